@@ -0,0 +1,118 @@
+use std::fmt::Display;
+
+/// A puzzle answer, integers that don't fit an `i64` are kept as `BigInt`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i64),
+    BigInt(i128),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int(n) => write!(f, "{}", n),
+            Self::BigInt(n) => write!(f, "{}", n),
+            Self::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! impl_from_small {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Self::Int(value.into())
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_wide {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    match i64::try_from(value) {
+                        Ok(n) => Self::Int(n),
+                        Err(_) => Self::BigInt(value as i128),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_small!(u8, u16, u32, i8, i16, i32, i64);
+impl_from_wide!(u64, usize, isize, i128);
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        match i128::try_from(value) {
+            Ok(n) => n.into(),
+            Err(_) => Self::Text(value.to_string()),
+        }
+    }
+}
+
+impl From<num::BigInt> for Answer {
+    fn from(value: num::BigInt) -> Self {
+        match i128::try_from(&value) {
+            Ok(n) => n.into(),
+            Err(_) => Self::Text(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        match self {
+            Self::Int(n) => other.parse() == Ok(*n),
+            Self::BigInt(n) => other.parse() == Ok(*n),
+            Self::Text(s) => s == other,
+        }
+    }
+}
+
+impl PartialEq<Answer> for &str {
+    fn eq(&self, other: &Answer) -> bool {
+        other == self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_int() {
+        assert_eq!(Answer::from(42u32), Answer::Int(42));
+        assert_eq!(Answer::from(-7i64), Answer::Int(-7));
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInt(u64::MAX as i128));
+        assert_eq!(Answer::from(u128::MAX), Answer::Text(u128::MAX.to_string()));
+        let big = num::BigInt::from(u128::MAX) * 2u32;
+        assert_eq!(Answer::from(big.clone()), Answer::Text(big.to_string()));
+        assert_eq!(Answer::from(num::BigInt::from(-3i32)), Answer::Int(-3));
+    }
+
+    #[test]
+    fn test_compare_str() {
+        assert_eq!("952408144115", Answer::from(952408144115u64));
+        assert_eq!(Answer::from("abc"), "abc");
+        assert_ne!(Answer::from(1u8), "2");
+    }
+}
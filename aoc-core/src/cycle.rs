@@ -0,0 +1,96 @@
+//! Simulations that are too long to run but end up repeating themselves.
+
+use std::{collections::HashMap, hash::Hash};
+
+use tracing::debug;
+
+/// The states of a simulation up to the point where it starts repeating.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<S> {
+    /// The number of steps before the first state that repeats.
+    pub start: usize,
+    pub len: usize,
+    // every state from the initial one to the end of the first round of the cycle
+    states: Vec<S>,
+}
+
+impl<S: Clone + Hash + Eq> Cycle<S> {
+    /// Steps from `initial` until a state comes up a second time, which has to happen.
+    pub fn find(initial: S, step: impl FnMut(&S) -> S) -> Self {
+        match Self::find_within(initial, usize::MAX, step) {
+            Ok(cycle) => cycle,
+            Err(_) => unreachable!("ran out of steps without a repeat"),
+        }
+    }
+
+    /// [`Cycle::find`], giving up after `limit` steps with the state reached then.
+    fn find_within(initial: S, limit: usize, mut step: impl FnMut(&S) -> S) -> Result<Self, S> {
+        let mut seen = HashMap::from([(initial.clone(), 0)]);
+        let mut states = vec![initial];
+        for i in 1..=limit {
+            let next = step(&states[i - 1]);
+            if let Some(&start) = seen.get(&next) {
+                debug!(start, len = i - start, "states repeat");
+                return Ok(Self {
+                    start,
+                    len: i - start,
+                    states,
+                });
+            }
+            seen.insert(next.clone(), i);
+            states.push(next);
+        }
+        Err(states.pop().expect("starts with the initial state"))
+    }
+
+    /// The state after `n` steps from the initial one.
+    pub fn state_after(&self, n: usize) -> &S {
+        if n < self.states.len() {
+            &self.states[n]
+        } else {
+            &self.states[self.start + (n - self.start) % self.len]
+        }
+    }
+}
+
+/// The state `n` steps from `initial`, skipping ahead once the states repeat.
+pub fn state_after<S: Clone + Hash + Eq>(initial: S, n: usize, step: impl FnMut(&S) -> S) -> S {
+    match Cycle::find_within(initial, n, step) {
+        Ok(cycle) => cycle.state_after(n).clone(),
+        Err(state) => state,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 1, 2, 4, 8, 16, 9, 18, 13, 3, 6, 12, 1 under doubling mod 23 after a lead in of 0 -> 1
+    fn step(n: &u32) -> u32 {
+        if *n == 0 {
+            1
+        } else {
+            n * 2 % 23
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        let cycle = Cycle::find(0, step);
+        assert_eq!((cycle.start, cycle.len), (1, 11));
+        assert_eq!(*cycle.state_after(0), 0);
+        assert_eq!(*cycle.state_after(3), 4);
+        assert_eq!(*cycle.state_after(12), 1);
+        assert_eq!(*cycle.state_after(1_000_003), 8);
+    }
+
+    #[test]
+    fn test_state_after() {
+        let naive = |n| (0..n).fold(0, |s, _| step(&s));
+        for n in [0, 1, 5, 11, 12, 13, 100, 1001] {
+            assert_eq!(state_after(0, n, step), naive(n), "after {}", n);
+        }
+        // the states never repeat within the steps asked for
+        assert_eq!(state_after(0u64, 5, |n| n + 1), 5);
+    }
+}
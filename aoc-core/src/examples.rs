@@ -0,0 +1,214 @@
+//! Example tests driven by files. A day keeps its examples as `examples/<name>.in`
+//! with the expected answers next to them in `<name>.part1` and `<name>.part2`.
+//! The build script calls [`generate`], which turns every answer file into a test,
+//! and the crate pulls them in with [`examples!`](crate::examples!).
+//!
+//! A part that isn't solved yet gets a `<name>.part1.ignore` file next to its
+//! answer, which makes the test `#[ignore]` with the file's text as the reason.
+
+use std::{
+    env,
+    ffi::OsString,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::Solution;
+
+/// Next to the crate's `Cargo.toml`.
+pub const DIR: &str = "examples";
+const GENERATED: &str = "examples.rs";
+
+/// Writes the tests for the crate being built, to be called from its `build.rs`.
+pub fn generate() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("run from a build script");
+    let out_dir = env::var("OUT_DIR").expect("run from a build script");
+    println!("cargo:rerun-if-changed={}", DIR);
+
+    let tests = render(&Path::new(&manifest_dir).join(DIR)).unwrap_or_else(|err| panic!("{}", err));
+    fs::write(Path::new(&out_dir).join(GENERATED), tests)
+        .expect("could not write the example tests");
+}
+
+/// The answer files in `dir` with their part, by name so the tests come out in a stable order.
+fn answers(dir: &Path) -> Vec<(PathBuf, u8)> {
+    let mut answers = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let part = match path.extension()?.to_str()? {
+                "part1" => 1,
+                "part2" => 2,
+                _ => return None,
+            };
+            Some((path, part))
+        })
+        .collect::<Vec<_>>();
+    answers.sort();
+    answers
+}
+
+// test names come from file names, which may not be identifiers
+fn test_name(stem: &str, part: u8) -> String {
+    let stem = stem
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    format!("{}_part{}", stem, part)
+}
+
+/// One test per answer file in `dir`, none when there is no such directory.
+pub fn render(dir: &Path) -> Result<String, String> {
+    let mut tests = String::new();
+    for (answer, part) in answers(dir) {
+        let input = answer.with_extension("in");
+        if !input.is_file() {
+            return Err(format!(
+                "{} has no input, expected {}",
+                answer.display(),
+                input.display()
+            ));
+        }
+        let mut marker = OsString::from(answer.as_os_str());
+        marker.push(".ignore");
+        let marker = PathBuf::from(marker);
+        if marker.is_file() {
+            let reason = fs::read_to_string(&marker)
+                .map_err(|err| format!("could not read {}: {}", marker.display(), err))?;
+            let reason = match reason.trim() {
+                "" => "not solved yet",
+                reason => reason,
+            };
+            writeln!(tests, "#[ignore = {:?}]", reason).unwrap();
+        }
+        let stem = answer.file_stem().unwrap_or_default().to_string_lossy();
+        writeln!(
+            tests,
+            "#[test]\nfn {}() {{\n    ::aoc_core::examples::check::<Example>({}, include_str!({:?}), include_str!({:?}));\n}}\n",
+            test_name(&stem, part),
+            part,
+            input.to_string_lossy(),
+            answer.to_string_lossy(),
+        )
+        .unwrap();
+    }
+    Ok(tests)
+}
+
+/// Asserts that `S` answers `part` of `input` with `expected`, ignoring surrounding
+/// whitespace.
+pub fn check<S: Solution>(part: u8, input: &str, expected: &str) {
+    let res = match part {
+        1 => S::solve_part1(input),
+        _ => S::solve_part2(input),
+    };
+    let answer = res.unwrap_or_else(|err| panic!("{}", err));
+    assert_eq!(
+        answer.to_string(),
+        expected.trim(),
+        "day {:02} part {}",
+        S::DAY,
+        part
+    );
+}
+
+/// Includes the tests [`generate`] wrote for the crate's examples, run against `$solution`.
+#[macro_export]
+macro_rules! examples {
+    ($solution:ident) => {
+        #[cfg(test)]
+        mod examples {
+            #[allow(dead_code)]
+            type Example = super::$solution;
+
+            include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Answer, ParseError};
+
+    use super::*;
+
+    struct Example;
+
+    impl Solution for Example {
+        const DAY: u8 = 0;
+
+        type Input<'a> = Vec<&'a str>;
+
+        fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+            Ok(input.lines().collect())
+        }
+
+        fn part1(input: &Self::Input<'_>) -> Answer {
+            input.len().into()
+        }
+
+        fn part2(_input: &Self::Input<'_>) -> Answer {
+            todo!()
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(render(&dir.path().join("missing")).unwrap(), "");
+
+        fs::write(dir.path().join("b.in"), "x").unwrap();
+        fs::write(dir.path().join("b.part2"), "1").unwrap();
+        fs::write(dir.path().join("a-small.in"), "x").unwrap();
+        fs::write(dir.path().join("a-small.part1"), "1").unwrap();
+        fs::write(dir.path().join("notes.md"), "").unwrap();
+        let tests = render(dir.path()).unwrap();
+        let names = tests
+            .lines()
+            .filter_map(|line| line.strip_prefix("fn "))
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["a_small_part1() {", "b_part2() {"]);
+        assert!(tests.contains(&format!(
+            "check::<Example>(1, include_str!({:?})",
+            dir.path().join("a-small.in").to_string_lossy()
+        )));
+        assert!(!tests.contains("#[ignore"));
+
+        fs::write(dir.path().join("b.part2.ignore"), "").unwrap();
+        fs::write(dir.path().join("a-small.part1.ignore"), "needs \"x\"\n").unwrap();
+        let tests = render(dir.path()).unwrap();
+        assert!(tests.starts_with("#[ignore = \"needs \\\"x\\\"\"]\n#[test]\nfn a_small_part1()"));
+        assert!(tests.contains("#[ignore = \"not solved yet\"]\n#[test]\nfn b_part2()"));
+
+        fs::write(dir.path().join("c.part1"), "1").unwrap();
+        assert!(render(dir.path())
+            .unwrap_err()
+            .contains("c.part1 has no input"));
+    }
+
+    #[test]
+    fn test_check() {
+        check::<Example>(1, "a\nb\n", "2\n");
+    }
+
+    // unsolved parts are marked as such, not passed
+    #[test]
+    #[should_panic(expected = "not yet implemented")]
+    fn test_check_unimplemented() {
+        check::<Example>(2, "a\nb\n", "2\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "day 00 part 1")]
+    fn test_check_wrong() {
+        check::<Example>(1, "a\nb\n", "3");
+    }
+}
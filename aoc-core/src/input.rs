@@ -0,0 +1,387 @@
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, IsTerminal, Read},
+    path::{Path, PathBuf},
+    process,
+};
+
+use clap::{value_parser, ArgAction, Parser};
+
+use crate::{
+    solution::{Options, Recording},
+    trace,
+};
+
+/// Directory holding `day-NN.txt` files, checked before the conventional `inputs/` folder.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where a puzzle input was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Path(PathBuf),
+    Stdin,
+    Env(PathBuf),
+    Conventional(PathBuf),
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Path(path) => write!(f, "{}", path.display()),
+            Self::Stdin => write!(f, "stdin"),
+            Self::Env(path) => write!(f, "${} ({})", INPUT_DIR_VAR, path.display()),
+            Self::Conventional(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Input {
+    pub source: Source,
+    pub text: String,
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// A location we were told to use exists but could not be read.
+    Read { source: Source, error: io::Error },
+    /// Nothing was found, `searched` lists every location in resolution order.
+    NotFound { day: u8, searched: Vec<String> },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read { source, error } => write!(f, "could not read {}: {}", source, error),
+            Self::NotFound { day, searched } => {
+                write!(f, "no input found for day {:02}, searched:", day)?;
+                for location in searched {
+                    write!(f, "\n  - {}", location)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Resolves a day's puzzle input at runtime, in order from:
+/// an explicit path, piped stdin, `$AOC_INPUT_DIR/day-NN.txt`
+/// and `inputs/day-NN.txt` in the current directory or any of its parents.
+#[derive(Debug, Clone)]
+pub struct InputLoader {
+    day: u8,
+    path: Option<PathBuf>,
+    stdin: bool,
+    env_dir: Option<PathBuf>,
+    search_from: Option<PathBuf>,
+}
+
+impl InputLoader {
+    pub fn new(day: u8) -> Self {
+        Self {
+            day,
+            path: None,
+            stdin: !io::stdin().is_terminal(),
+            env_dir: env::var_os(INPUT_DIR_VAR).map(PathBuf::from),
+            search_from: env::current_dir().ok(),
+        }
+    }
+
+    pub fn path(mut self, path: Option<PathBuf>) -> Self {
+        self.path = path;
+        self
+    }
+
+    /// Whether stdin may be read, by default only when it isn't a terminal.
+    pub fn stdin(mut self, enabled: bool) -> Self {
+        self.stdin = enabled;
+        self
+    }
+
+    pub fn env_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.env_dir = dir;
+        self
+    }
+
+    pub fn search_from(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.search_from = Some(dir.into());
+        self
+    }
+
+    pub fn file_name(&self) -> String {
+        format!("day-{:02}.txt", self.day)
+    }
+
+    /// Where a downloaded input should be stored so that [`load`](Self::load)
+    /// finds it: `$AOC_INPUT_DIR`, else the nearest existing `inputs/` folder,
+    /// else `inputs/` in the directory the search starts from.
+    pub fn cache_dir(&self) -> PathBuf {
+        if let Some(dir) = &self.env_dir {
+            return dir.clone();
+        }
+        let start = self.search_from.clone().unwrap_or_default();
+        start
+            .ancestors()
+            .map(|dir| dir.join("inputs"))
+            .find(|dir| dir.is_dir())
+            .unwrap_or_else(|| start.join("inputs"))
+    }
+
+    pub fn load(&self) -> Result<Input, InputError> {
+        let mut searched = vec![];
+
+        match &self.path {
+            Some(path) => return read(Source::Path(path.clone()), path),
+            None => searched.push("--input: not given".to_string()),
+        }
+
+        if self.stdin {
+            let mut text = String::new();
+            io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|error| InputError::Read {
+                    source: Source::Stdin,
+                    error,
+                })?;
+            if !text.is_empty() {
+                return Ok(Input {
+                    source: Source::Stdin,
+                    text,
+                });
+            }
+            searched.push("stdin: empty".to_string());
+        } else {
+            searched.push("stdin: not piped".to_string());
+        }
+
+        match &self.env_dir {
+            Some(dir) => {
+                let path = dir.join(self.file_name());
+                if path.is_file() {
+                    return read(Source::Env(path.clone()), &path);
+                }
+                searched.push(format!("${}: {} not found", INPUT_DIR_VAR, path.display()));
+            }
+            None => searched.push(format!("${}: not set", INPUT_DIR_VAR)),
+        }
+
+        if let Some(dir) = &self.search_from {
+            for dir in dir.ancestors() {
+                let path = dir.join("inputs").join(self.file_name());
+                if path.is_file() {
+                    return read(Source::Conventional(path.clone()), &path);
+                }
+            }
+            searched.push(format!(
+                "inputs/{} in {} and its parents: not found",
+                self.file_name(),
+                dir.display()
+            ));
+        }
+
+        Err(InputError::NotFound {
+            day: self.day,
+            searched,
+        })
+    }
+}
+
+fn read(source: Source, path: &Path) -> Result<Input, InputError> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Input { source, text }),
+        Err(error) => Err(InputError::Read { source, error }),
+    }
+}
+
+/// Command line of the per-day binaries.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Parser)]
+#[command(about = "Solves a part of the day's puzzle")]
+pub struct Args {
+    /// Puzzle input, otherwise stdin, `$AOC_INPUT_DIR` or `inputs/day-NN.txt`
+    pub path: Option<PathBuf>,
+    /// Show what the solution is doing on stderr, repeat for more detail
+    #[arg(short, long, action = ArgAction::Count)]
+    pub verbose: u8,
+    /// Draw what the part computed
+    #[arg(long)]
+    pub render: bool,
+    /// Save an animation of the part: a .gif, an animated .png or a directory of frames
+    #[arg(long, value_name = "PATH")]
+    pub record: Option<PathBuf>,
+    /// Pixels per grid cell in the animation
+    #[arg(long, value_name = "N", requires = "record", value_parser = value_parser!(u32).range(1..))]
+    pub cell_size: Option<u32>,
+    /// Save a picture of what the part computed
+    #[arg(long, value_name = "PATH")]
+    pub export: Option<PathBuf>,
+}
+
+impl Args {
+    /// What to do with the part besides solving it.
+    pub fn options(&self) -> Options {
+        Options {
+            render: self.render,
+            record: self.record.clone().map(|path| Recording {
+                path,
+                cell_size: self.cell_size,
+            }),
+            export: self.export.clone(),
+        }
+    }
+
+    /// The day's input, after setting up diagnostics as asked for. Exits the
+    /// process with the search report if no input can be found.
+    pub fn input(&self, day: u8) -> String {
+        trace::init(self.verbose);
+        match InputLoader::new(day).path(self.path.clone()).load() {
+            Ok(input) => {
+                tracing::debug!(source = %input.source, "input loaded");
+                input.text
+            }
+            Err(err) => {
+                eprintln!("{}", err);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loader(day: u8, root: &Path) -> InputLoader {
+        InputLoader::new(day)
+            .stdin(false)
+            .env_dir(None)
+            .search_from(root)
+    }
+
+    #[test]
+    fn test_explicit_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("custom.txt");
+        fs::write(&path, "abc").unwrap();
+
+        let input = loader(1, dir.path())
+            .path(Some(path.clone()))
+            .load()
+            .unwrap();
+        assert_eq!(input.source, Source::Path(path));
+        assert_eq!(input.text, "abc");
+    }
+
+    #[test]
+    fn test_explicit_path_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let res = loader(1, dir.path())
+            .path(Some(dir.path().join("nope.txt")))
+            .load();
+        assert!(matches!(res, Err(InputError::Read { .. })));
+    }
+
+    #[test]
+    fn test_env_before_conventional() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("env")).unwrap();
+        fs::create_dir_all(dir.path().join("inputs")).unwrap();
+        fs::write(dir.path().join("env/day-05.txt"), "env").unwrap();
+        fs::write(dir.path().join("inputs/day-05.txt"), "conventional").unwrap();
+
+        let input = loader(5, dir.path())
+            .env_dir(Some(dir.path().join("env")))
+            .load()
+            .unwrap();
+        assert_eq!(input.text, "env");
+
+        let input = loader(5, dir.path()).load().unwrap();
+        assert_eq!(input.text, "conventional");
+    }
+
+    #[test]
+    fn test_conventional_in_parent() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("inputs")).unwrap();
+        fs::create_dir_all(dir.path().join("day-12/src")).unwrap();
+        fs::write(dir.path().join("inputs/day-12.txt"), "springs").unwrap();
+
+        let input = loader(12, &dir.path().join("day-12/src")).load().unwrap();
+        assert_eq!(
+            input.source,
+            Source::Conventional(dir.path().join("inputs/day-12.txt"))
+        );
+        assert_eq!(input.text, "springs");
+    }
+
+    #[test]
+    fn test_cache_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("day-01/src")).unwrap();
+        let nested = dir.path().join("day-01/src");
+        assert_eq!(loader(1, &nested).cache_dir(), nested.join("inputs"));
+
+        fs::create_dir_all(dir.path().join("inputs")).unwrap();
+        assert_eq!(loader(1, &nested).cache_dir(), dir.path().join("inputs"));
+
+        let env = dir.path().join("env");
+        let loader = loader(1, &nested).env_dir(Some(env.clone()));
+        assert_eq!(loader.cache_dir(), env);
+    }
+
+    #[test]
+    fn test_not_found_lists_locations() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = loader(3, dir.path()).load().unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.starts_with("no input found for day 03, searched:"));
+        assert!(msg.contains("--input: not given"));
+        assert!(msg.contains("stdin: not piped"));
+        assert!(msg.contains("$AOC_INPUT_DIR: not set"));
+        assert!(msg.contains("inputs/day-03.txt"));
+    }
+
+    #[test]
+    fn test_args() {
+        let parse = |args: &[&str]| Args::try_parse_from(["day-01-part1"].iter().chain(args));
+        assert_eq!(parse(&[]).unwrap(), Args::default());
+        assert_eq!(
+            parse(&["-vv", "in.txt", "-v"]).unwrap(),
+            Args {
+                path: Some(PathBuf::from("in.txt")),
+                verbose: 3,
+                ..Args::default()
+            }
+        );
+        assert!(parse(&["--render", "-v"]).unwrap().options().render);
+        assert_eq!(
+            parse(&["--cell-size", "8", "--record", "out.gif"])
+                .unwrap()
+                .options()
+                .record,
+            Some(Recording {
+                path: PathBuf::from("out.gif"),
+                cell_size: Some(8),
+            })
+        );
+        assert!(parse(&["--record"]).is_err());
+        assert_eq!(
+            parse(&["--export", "plan.svg", "in.txt"])
+                .unwrap()
+                .options()
+                .export,
+            Some(PathBuf::from("plan.svg"))
+        );
+        assert!(parse(&["--export"]).is_err());
+        assert!(parse(&["--record", "out.gif", "--cell-size", "0"]).is_err());
+        assert!(parse(&["--cell-size", "2"]).is_err());
+        assert_eq!(parse(&["--verbose"]).unwrap().verbose, 1);
+        // a lone dash is a file name like any other
+        assert_eq!(parse(&["-"]).unwrap().path, Some(PathBuf::from("-")));
+        assert!(parse(&["-x"]).is_err());
+        assert!(parse(&["a.txt", "b.txt"]).is_err());
+    }
+}
@@ -0,0 +1,13 @@
+pub mod answer;
+pub mod cycle;
+pub mod examples;
+pub mod input;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod solution;
+pub mod trace;
+
+pub use answer::Answer;
+pub use parse::ParseError;
+pub use solution::Solution;
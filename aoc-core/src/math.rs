@@ -0,0 +1,157 @@
+//! Number theory for puzzles about things lining up. The functions work on any
+//! signed integer: `i64` or `i128` for speed, [`BigInt`] once those overflow.
+//! The `_u128` variants take unsigned values that don't fit an `i128`.
+
+pub use num::BigInt;
+use num::{integer::Roots, Integer, Signed, ToPrimitive};
+
+/// `(g, x, y)` with `g = gcd(a, b) = a * x + b * y` and `g` not negative.
+pub fn extended_gcd<T: Integer + Signed + Clone>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+    while !r.is_zero() {
+        let q = old_r.clone() / r.clone();
+        (old_r, r) = (r.clone(), old_r - q.clone() * r);
+        (old_x, x) = (x.clone(), old_x - q.clone() * x);
+        (old_y, y) = (y.clone(), old_y - q * y);
+    }
+    if old_r.is_negative() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `x` in `0..m` with `a * x ≡ 1 (mod m)`, `None` when `a` and `m` share a factor.
+pub fn mod_inverse<T: Integer + Signed + Clone>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(a, m.clone());
+    g.is_one().then(|| x.mod_floor(&m))
+}
+
+/// Combines congruences `x ≡ r (mod m)` into a single one, returned as `(r, m)`
+/// with `m` the lcm of the moduli and `r` in `0..m`. The moduli have to be
+/// positive but need not be coprime, `None` when the congruences contradict
+/// each other.
+pub fn crt<T: Integer + Signed + Clone>(
+    congruences: impl IntoIterator<Item = (T, T)>,
+) -> Option<(T, T)> {
+    congruences
+        .into_iter()
+        .try_fold((T::zero(), T::one()), |(r1, m1), (r2, m2)| {
+            let (g, p, _) = extended_gcd(m1.clone(), m2.clone());
+            let diff = r2 - r1.clone();
+            if !diff.is_multiple_of(&g) {
+                return None;
+            }
+            // m1 * p ≡ g (mod m2), so stepping k times m1 from r1 closes the gap
+            let k = (diff / g.clone() * p).mod_floor(&(m2.clone() / g.clone()));
+            let m = m1.clone() / g * m2;
+            Some(((r1 + m1 * k).mod_floor(&m), m))
+        })
+}
+
+/// [`extended_gcd`] of unsigned values, the coefficients can be negative so
+/// they come as [`BigInt`].
+pub fn extended_gcd_u128(a: u128, b: u128) -> (u128, BigInt, BigInt) {
+    let (g, x, y) = extended_gcd(BigInt::from(a), BigInt::from(b));
+    (g.to_u128().expect("gcd fits its arguments"), x, y)
+}
+
+/// [`mod_inverse`] of unsigned values.
+pub fn mod_inverse_u128(a: u128, m: u128) -> Option<u128> {
+    mod_inverse(BigInt::from(a), BigInt::from(m)).map(|x| x.to_u128().expect("below m"))
+}
+
+/// [`crt`] of unsigned congruences, like the `checked_` operations `None` is
+/// also returned when the combined modulus overflows a `u128`.
+pub fn crt_u128(congruences: impl IntoIterator<Item = (u128, u128)>) -> Option<(u128, u128)> {
+    let congruences = congruences
+        .into_iter()
+        .map(|(r, m)| (BigInt::from(r), BigInt::from(m)));
+    let (r, m) = crt(congruences)?;
+    Some((r.to_u128()?, m.to_u128()?))
+}
+
+/// The largest `r` with `r * r <= n`, exact where going through `f64` is not.
+pub fn isqrt<T: Roots>(n: T) -> T {
+    n.sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240i64, 46), (-240, 46), (17, 5), (0, 7), (7, 0), (12, -18)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, a.gcd(&b), "gcd({}, {})", a, b);
+            assert_eq!(a * x + b * y, g, "gcd({}, {})", a, b);
+        }
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+        let m = BigInt::from(10u32).pow(30u32) + 7u32;
+        let inverse = mod_inverse(BigInt::from(3u32), m.clone()).unwrap();
+        assert_eq!(inverse * 3u32 % m, BigInt::from(1u32));
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // not coprime, but compatible
+        assert_eq!(crt([(2i64, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1i64, 4), (2, 6)]), None);
+        assert_eq!(crt::<i64>([]), Some((0, 1)));
+        assert_eq!(crt([(-1i64, 5)]), Some((4, 5)));
+
+        // the lcm overflows an i64
+        let primes = [1_000_000_007i128, 998_244_353, 1_000_000_009];
+        let (r, m) = crt(primes.map(|p| (p - 1, p))).unwrap();
+        assert_eq!(m, primes.iter().product::<i128>());
+        assert_eq!(r, m - 1);
+
+        let big = crt(primes.map(|p| (BigInt::from(5u32), BigInt::from(p).pow(3u32)))).unwrap();
+        assert_eq!(big.0, BigInt::from(5u32));
+    }
+
+    #[test]
+    fn test_u128() {
+        let (g, x, y) = extended_gcd_u128(u128::MAX, 6);
+        assert_eq!(g, 3);
+        assert_eq!(x * u128::MAX + y * 6u32, BigInt::from(3u32));
+        assert_eq!(
+            mod_inverse_u128(u128::MAX - 1, u128::MAX),
+            Some(u128::MAX - 1)
+        );
+        assert_eq!(mod_inverse_u128(6, 9), None);
+
+        let p = u64::MAX as u128 - 58; // the largest 64 bit prime
+        assert_eq!(
+            crt_u128([(1, p), (2, p + 2)]).map(|(r, m)| (r % p, r % (p + 2), m)),
+            Some((1, 2, p * (p + 2)))
+        );
+        assert_eq!(crt_u128([(1, 4), (2, 6)]), None);
+        // an lcm past u128::MAX
+        assert_eq!(crt_u128([(0, u128::MAX), (0, u128::MAX - 1)]), None);
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(isqrt(0u64), 0);
+        assert_eq!(isqrt(15u64), 3);
+        assert_eq!(isqrt(16u64), 4);
+        let n = u64::MAX as u128 * u64::MAX as u128;
+        assert_eq!(isqrt(n), u64::MAX as u128);
+        assert_eq!(isqrt(n - 1), u64::MAX as u128 - 1);
+        assert_eq!(
+            isqrt(BigInt::from(10u32).pow(40u32)),
+            BigInt::from(10u32).pow(20u32)
+        );
+    }
+}
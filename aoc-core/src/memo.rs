@@ -0,0 +1,121 @@
+//! Caching for recursive solutions that keep asking the same question, like
+//! counting the ways to finish from some position.
+
+use std::{collections::HashMap, fmt::Display, hash::Hash};
+
+/// How well a [`Memo`] has been doing.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: usize,
+    pub misses: usize,
+    /// The number of values kept.
+    pub size: usize,
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lookups = self.hits + self.misses;
+        let rate = if lookups == 0 {
+            0.0
+        } else {
+            100.0 * self.hits as f64 / lookups as f64
+        };
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate), {} cached",
+            self.hits, self.misses, rate, self.size
+        )
+    }
+}
+
+/// Values of a function by its arguments, usually a tuple of them.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    values: HashMap<K, V>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            values: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The value for `key`, computed by `f` the first time it is asked for.
+    /// `f` gets the memo back so it can recurse through it.
+    pub fn get_or_insert_with(&mut self, key: K, f: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.values.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+        let value = f(self);
+        self.values.insert(key, value.clone());
+        value
+    }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            hits: self.hits,
+            misses: self.misses,
+            size: self.values.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the paths through an n by m grid going only right or down
+    fn paths(n: u64, m: u64, memo: &mut Memo<(u64, u64), u64>) -> u64 {
+        memo.get_or_insert_with((n, m), |memo| {
+            if n == 0 || m == 0 {
+                1
+            } else {
+                paths(n - 1, m, memo) + paths(n, m - 1, memo)
+            }
+        })
+    }
+
+    #[test]
+    fn test_memo() {
+        let mut memo = Memo::new();
+        assert_eq!(paths(16, 16, &mut memo), 601_080_390);
+        let stats = memo.stats();
+        assert_eq!(stats.size, 17 * 17 - 1);
+        assert_eq!(stats.misses, stats.size);
+        // the 16 * 16 points away from the edges make two calls each, on top of the first
+        assert_eq!(stats.hits + stats.misses, 2 * 16 * 16 + 1);
+
+        assert_eq!(paths(16, 16, &mut memo), 601_080_390);
+        assert_eq!(memo.stats().hits, stats.hits + 1);
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats {
+            hits: 3,
+            misses: 1,
+            size: 1,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "3 hits, 1 misses (75.0% hit rate), 1 cached"
+        );
+        assert_eq!(
+            Stats::default().to_string(),
+            "0 hits, 0 misses (0.0% hit rate), 0 cached"
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use std::{borrow::Cow, fmt::Display};
+
+use nom::{
+    character::complete::multispace0,
+    combinator::eof,
+    error::{ContextError, ErrorKind, FromExternalError},
+    sequence::terminated,
+    Offset, Parser,
+};
+
+/// Parser result using [`Error`], a drop-in for `nom::IResult`.
+pub type IResult<I, O, E = Error<I>> = nom::IResult<I, O, E>;
+
+/// A puzzle input that could not be parsed, positions are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    /// The rest of the offending line, empty at the end of the input.
+    pub found: String,
+}
+
+impl ParseError {
+    /// `at` must be a subslice of `input` starting where parsing failed.
+    pub fn new(day: u8, input: &str, at: &str, expected: impl Into<String>) -> Self {
+        let start = input.as_ptr() as usize;
+        let offset = match (at.as_ptr() as usize).checked_sub(start) {
+            Some(offset) if offset <= input.len() => input.offset(at),
+            _ => input.len(),
+        };
+        Self::at_offset(day, input, offset, expected)
+    }
+
+    pub fn at_offset(day: u8, input: &str, offset: usize, expected: impl Into<String>) -> Self {
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let column = before[line_start..].chars().count() + 1;
+        let found = input[offset..]
+            .lines()
+            .next()
+            .unwrap_or("")
+            .trim_end_matches('\r')
+            .to_string();
+
+        Self {
+            day,
+            line,
+            column,
+            expected: expected.into(),
+            found,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {:02}: invalid input at line {}, column {}: expected {}, found ",
+            self.day, self.line, self.column, self.expected
+        )?;
+        if self.found.is_empty() {
+            write!(f, "end of line")
+        } else {
+            write!(f, "{:?}", self.found)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// nom error that remembers what was expected, wrap parsers in
+/// `nom::error::context` to give a better description than the [`ErrorKind`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error<I> {
+    pub input: I,
+    pub expected: Cow<'static, str>,
+}
+
+fn describe(kind: ErrorKind) -> Cow<'static, str> {
+    use ErrorKind::*;
+    let what = match kind {
+        Digit => "a number",
+        HexDigit => "a hex digit",
+        Alpha => "a letter",
+        AlphaNumeric => "a letter or digit",
+        Space => "a space",
+        MultiSpace => "whitespace",
+        CrLf => "a newline",
+        Eof => "end of input",
+        Tag => "a literal",
+        OneOf => "one of the allowed characters",
+        Char => "a character",
+        other => return Cow::Owned(other.description().to_lowercase()),
+    };
+    Cow::Borrowed(what)
+}
+
+impl<I> nom::error::ParseError<I> for Error<I> {
+    fn from_error_kind(input: I, kind: ErrorKind) -> Self {
+        Self {
+            input,
+            expected: describe(kind),
+        }
+    }
+
+    // the innermost error is the most specific one
+    fn append(_input: I, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: I, c: char) -> Self {
+        Self {
+            input,
+            expected: Cow::Owned(format!("{:?}", c)),
+        }
+    }
+}
+
+impl<I> ContextError<I> for Error<I> {
+    fn add_context(_input: I, ctx: &'static str, mut other: Self) -> Self {
+        other.expected = Cow::Borrowed(ctx);
+        other
+    }
+}
+
+impl<I, E> FromExternalError<I, E> for Error<I> {
+    fn from_external_error(input: I, kind: ErrorKind, _e: E) -> Self {
+        <Self as nom::error::ParseError<I>>::from_error_kind(input, kind)
+    }
+}
+
+impl<'a> Error<&'a str> {
+    pub fn into_parse_error(self, day: u8, input: &'a str) -> ParseError {
+        ParseError::new(day, input, self.input, self.expected)
+    }
+}
+
+/// The [`ParseError`] for a failed parser, `input` being what it points into.
+pub fn convert<'a>(day: u8, input: &'a str, err: nom::Err<Error<&'a str>>) -> ParseError {
+    match err {
+        nom::Err::Error(err) | nom::Err::Failure(err) => err.into_parse_error(day, input),
+        nom::Err::Incomplete(_) => ParseError::at_offset(day, input, input.len(), "more input"),
+    }
+}
+
+/// Runs `parser` over the whole input. Trailing whitespace is fine,
+/// anything else left over is an error rather than silently ignored.
+pub fn parse_all<'a, O>(
+    day: u8,
+    input: &'a str,
+    parser: impl Parser<&'a str, O, Error<&'a str>>,
+) -> Result<O, ParseError> {
+    terminated(parser, terminated(multispace0, eof))(input)
+        .map(|(_, output)| output)
+        .map_err(|err| convert(day, input, err))
+}
+
+/// Runs `parser` on every line, each line has to be consumed entirely.
+/// Errors point into `input`, so `lines` has to be a subslice of it.
+pub fn parse_lines<'a, O>(
+    day: u8,
+    input: &'a str,
+    lines: &'a str,
+    mut parser: impl Parser<&'a str, O, Error<&'a str>>,
+) -> Result<Vec<O>, ParseError> {
+    lines
+        .trim_end()
+        .lines()
+        .map(|line| match parser.parse(line) {
+            Ok(("", output)) => Ok(output),
+            Ok((rest, _)) => Err(ParseError::new(day, input, rest, "end of line")),
+            Err(err) => Err(convert(day, input, err)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        bytes::complete::tag, character::complete, error::context, multi::separated_list1,
+        sequence::preceded,
+    };
+
+    use super::*;
+
+    fn numbers(input: &str) -> IResult<&str, Vec<u32>> {
+        preceded(
+            context("\"nums: \"", tag("nums: ")),
+            separated_list1(tag(" "), complete::u32),
+        )(input)
+    }
+
+    #[test]
+    fn test_parse_all() {
+        assert_eq!(parse_all(1, "nums: 1 2 3\n\n", numbers), Ok(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_trailing_garbage() {
+        let err = parse_all(4, "nums: 1 2 3\nnums: 4", numbers).unwrap_err();
+        assert_eq!(err.day, 4);
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "end of input");
+        assert_eq!(err.found, "nums: 4");
+    }
+
+    #[test]
+    fn test_context() {
+        let err = parse_all(2, "\nnum: 1", preceded(tag("\n"), numbers)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "\"nums: \"");
+        assert_eq!(err.found, "num: 1");
+        assert_eq!(
+            err.to_string(),
+            "day 02: invalid input at line 2, column 1: expected \"nums: \", found \"num: 1\""
+        );
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let input = "nums: 1 2\r\nnums: 3\n\n";
+        assert_eq!(
+            parse_lines(1, input, input, numbers),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+
+        let input = "nums: 1 2\nnums: 3 x\n";
+        let err = parse_lines(1, input, input, numbers).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 8));
+        assert_eq!(err.expected, "end of line");
+        assert_eq!(err.found, " x");
+    }
+
+    #[test]
+    fn test_truncated() {
+        let err = parse_all(3, "nums: ", numbers).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 7));
+        assert_eq!(err.expected, "a number");
+        assert!(err.to_string().ends_with("found end of line"));
+    }
+
+    #[test]
+    fn test_position() {
+        let input = "ab\ncdé\r\nfgh";
+        let err = ParseError::new(9, input, &input[9..], "x");
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.found, "fgh");
+        let err = ParseError::new(9, input, &input[4..], "x");
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.found, "dé");
+    }
+}
@@ -0,0 +1,114 @@
+use std::{
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use tracing::{debug, error, info, info_span, warn};
+
+use crate::{Answer, ParseError};
+
+/// Where an animation of a part is saved, see [`Solution::record`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recording {
+    pub path: PathBuf,
+    /// Pixels per grid cell, if not the day's own choice.
+    pub cell_size: Option<u32>,
+}
+
+/// What to do with a part besides solving it, see [`Solution::solve_with`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// Draw what the part computed, see [`Solution::render`].
+    pub render: bool,
+    /// Animate what the part computed, see [`Solution::record`].
+    pub record: Option<Recording>,
+    /// Save a picture of what the part computed, see [`Solution::export`].
+    pub export: Option<PathBuf>,
+}
+
+/// A day's puzzle: the input is parsed once and both parts are solved from it.
+pub trait Solution {
+    const DAY: u8;
+
+    /// Parsed puzzle input, may borrow from the raw text.
+    type Input<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+
+    fn part1(input: &Self::Input<'_>) -> Answer;
+
+    fn part2(input: &Self::Input<'_>) -> Answer;
+
+    /// A picture of what `part` computed, drawn to stderr once it is solved when
+    /// [`Options::render`] is set. Coloured with ANSI escapes when `ansi` is set.
+    /// Days that can draw one do so behind their `visualize` feature.
+    fn render(_input: &Self::Input<'_>, _part: u8, _ansi: bool) -> Option<String> {
+        None
+    }
+
+    /// Saves an animation of how `part` got to its answer for [`Options::record`],
+    /// behind the same feature as [`Solution::render`].
+    fn record(_input: &Self::Input<'_>, _part: u8, _to: &Recording) -> Option<io::Result<()>> {
+        None
+    }
+
+    /// Saves a picture of what `part` computed to `path` for [`Options::export`],
+    /// in whatever format suits the day, with the same feature as
+    /// [`Solution::render`].
+    fn export(_input: &Self::Input<'_>, _part: u8, _path: &Path) -> Option<io::Result<()>> {
+        None
+    }
+
+    fn solve_part1(input: &str) -> Result<Answer, ParseError> {
+        Self::solve_with(input, 1, &Options::default())
+    }
+
+    fn solve_part2(input: &str) -> Result<Answer, ParseError> {
+        Self::solve_with(input, 2, &Options::default())
+    }
+
+    /// Solves `part`, then draws, records or exports it as `options` ask.
+    fn solve_with(input: &str, part: u8, options: &Options) -> Result<Answer, ParseError> {
+        match part {
+            1 => solve::<Self>(input, 1, Self::part1, options),
+            _ => solve::<Self>(input, 2, Self::part2, options),
+        }
+    }
+}
+
+// everything the part logs goes in a span saying which one it is
+fn solve<S: Solution + ?Sized>(
+    input: &str,
+    part: u8,
+    solve: impl for<'a> Fn(&S::Input<'a>) -> Answer,
+    options: &Options,
+) -> Result<Answer, ParseError> {
+    let _span = info_span!("solve", day = S::DAY, part).entered();
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    debug!(elapsed = ?start.elapsed(), "parsed");
+    let answer = solve(&input);
+    info!(elapsed = ?start.elapsed(), %answer, "solved");
+    if options.render {
+        match S::render(&input, part, io::stderr().is_terminal()) {
+            Some(picture) => eprint!("{}", picture),
+            None => warn!("nothing to render, this day may need the visualize feature"),
+        }
+    }
+    if let Some(to) = &options.record {
+        match S::record(&input, part, to) {
+            Some(Ok(())) => info!(path = %to.path.display(), "recorded"),
+            Some(Err(err)) => error!(path = %to.path.display(), %err, "recording failed"),
+            None => warn!("nothing to record, this day may need the visualize feature"),
+        }
+    }
+    if let Some(path) = &options.export {
+        match S::export(&input, part, path) {
+            Some(Ok(())) => info!(path = %path.display(), "exported"),
+            Some(Err(err)) => error!(path = %path.display(), %err, "export failed"),
+            None => warn!("nothing to export, this day may need the visualize feature"),
+        }
+    }
+    Ok(answer)
+}
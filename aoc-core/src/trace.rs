@@ -0,0 +1,42 @@
+//! Diagnostics from the solutions, emitted with `tracing` and written to
+//! stderr so they never end up mixed with an answer on stdout.
+//!
+//! Nothing below a warning is shown by default, each `-v` shows one more level:
+//! info, debug, then trace.
+
+use std::io::{self, IsTerminal};
+
+use tracing::level_filters::LevelFilter;
+
+/// The most detailed level shown for a number of `-v` flags.
+pub fn level(verbosity: u8) -> LevelFilter {
+    match verbosity {
+        0 => LevelFilter::WARN,
+        1 => LevelFilter::INFO,
+        2 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    }
+}
+
+/// Sets up printing events to stderr, does nothing if that was already done.
+pub fn init(verbosity: u8) {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(level(verbosity))
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .without_time()
+        .with_target(false)
+        .try_init();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level() {
+        assert_eq!(level(0), LevelFilter::WARN);
+        assert_eq!(level(2), LevelFilter::DEBUG);
+        assert_eq!(level(7), LevelFilter::TRACE);
+    }
+}
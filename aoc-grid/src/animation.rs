@@ -0,0 +1,268 @@
+//! Grids recorded frame by frame while a simulation runs, and saved as an
+//! animation: a GIF, an animated PNG, or a directory of numbered PNGs.
+//!
+//! Every cell is drawn as a square of one colour, picked from a palette by index.
+
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use aoc_core::solution::Recording;
+
+use crate::{render::Rgb, Grid, Pos};
+
+/// Shades of grey from black to white, for when no palette is given.
+const GREYS: [Rgb; 4] = [
+    Rgb(0, 0, 0),
+    Rgb(85, 85, 85),
+    Rgb(170, 170, 170),
+    Rgb(255, 255, 255),
+];
+
+/// Frames of a `width` by `height` grid, each cell a colour index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recorder {
+    width: usize,
+    height: usize,
+    cell_size: u32,
+    palette: Vec<Rgb>,
+    delay_ms: u16,
+    frames: Vec<Vec<u8>>,
+}
+
+impl Recorder {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cell_size: 4,
+            palette: GREYS.to_vec(),
+            delay_ms: 100,
+            frames: vec![],
+        }
+    }
+
+    /// The side of the square drawn for a cell, in pixels. A [`Recording`] asking
+    /// for another size overrides it.
+    pub fn cell_size(self, cell_size: u32) -> Self {
+        assert!(cell_size > 0, "cells have to be at least a pixel");
+        Self { cell_size, ..self }
+    }
+
+    /// The colours cells are drawn in, at most 256.
+    pub fn palette(self, palette: &[Rgb]) -> Self {
+        assert!(
+            (1..=256).contains(&palette.len()),
+            "{} colours in the palette",
+            palette.len()
+        );
+        Self {
+            palette: palette.to_vec(),
+            ..self
+        }
+    }
+
+    /// How long each frame is shown.
+    pub fn delay_ms(self, delay_ms: u16) -> Self {
+        Self { delay_ms, ..self }
+    }
+
+    /// Adds a frame with `color` giving the palette index of every cell.
+    pub fn record<T>(&mut self, grid: &Grid<T>, mut color: impl FnMut(Pos, &T) -> u8) {
+        assert_eq!(
+            (grid.width(), grid.height()),
+            (self.width, self.height),
+            "frame size"
+        );
+        let frame = grid
+            .iter()
+            .map(|(pos, value)| color(pos, value))
+            .collect::<Vec<_>>();
+        let colors = self.palette.len();
+        assert!(
+            frame.iter().all(|&i| (i as usize) < colors),
+            "a colour outside the palette of {}",
+            colors
+        );
+        self.frames.push(frame);
+    }
+
+    pub fn frames(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Saves the frames where `to` says: a path ending in `.gif` becomes a GIF,
+    /// one ending in `.png` an animated PNG, and anything else a directory of
+    /// `frame-0001.png` and so on.
+    pub fn save(&self, to: &Recording) -> io::Result<()> {
+        let resized;
+        let recorder = match to.cell_size {
+            Some(cell_size) => {
+                resized = self.clone().cell_size(cell_size);
+                &resized
+            }
+            None => self,
+        };
+        match to.path.extension().and_then(|e| e.to_str()) {
+            Some("gif") => recorder.write_gif(BufWriter::new(File::create(&to.path)?)),
+            Some("png") => recorder.write_apng(BufWriter::new(File::create(&to.path)?)),
+            _ => recorder.write_pngs(&to.path),
+        }
+    }
+
+    fn pixel_size(&self) -> (u32, u32) {
+        let side = |cells: usize| cells as u32 * self.cell_size;
+        (side(self.width), side(self.height))
+    }
+
+    // every cell blown up to a square, one palette index per pixel
+    fn pixels(&self, frame: &[u8]) -> Vec<u8> {
+        let size = self.cell_size as usize;
+        let mut pixels = Vec::with_capacity(frame.len() * size * size);
+        for row in frame.chunks(self.width.max(1)) {
+            let line = row
+                .iter()
+                .flat_map(|&i| std::iter::repeat_n(i, size))
+                .collect::<Vec<_>>();
+            for _ in 0..size {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        pixels
+    }
+
+    fn palette_bytes(&self) -> Vec<u8> {
+        self.palette
+            .iter()
+            .flat_map(|&Rgb(r, g, b)| [r, g, b])
+            .collect()
+    }
+
+    pub fn write_gif(&self, out: impl Write) -> io::Result<()> {
+        let (width, height) = self.pixel_size();
+        let (width, height) = match (u16::try_from(width), u16::try_from(height)) {
+            (Ok(width), Ok(height)) => (width, height),
+            _ => return Err(io::Error::other("too big for a GIF")),
+        };
+        let mut encoder = gif::Encoder::new(out, width, height, &self.palette_bytes())
+            .map_err(io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+        for frame in &self.frames {
+            let mut frame =
+                gif::Frame::from_indexed_pixels(width, height, self.pixels(frame), None);
+            // GIF delays are in hundredths of a second
+            frame.delay = self.delay_ms / 10;
+            encoder.write_frame(&frame).map_err(io::Error::other)?;
+        }
+        Ok(())
+    }
+
+    fn png_encoder<W: Write>(&self, out: W) -> png::Encoder<'_, W> {
+        let (width, height) = self.pixel_size();
+        let mut encoder = png::Encoder::new(out, width, height);
+        encoder.set_color(png::ColorType::Indexed);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_palette(self.palette_bytes());
+        encoder
+    }
+
+    pub fn write_apng(&self, out: impl Write) -> io::Result<()> {
+        if self.frames.is_empty() {
+            return Err(io::Error::other("no frames recorded"));
+        }
+        let mut encoder = self.png_encoder(out);
+        encoder
+            .set_animated(self.frames.len() as u32, 0)
+            .map_err(io::Error::other)?;
+        encoder
+            .set_frame_delay(self.delay_ms, 1000)
+            .map_err(io::Error::other)?;
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        for frame in &self.frames {
+            writer
+                .write_image_data(&self.pixels(frame))
+                .map_err(io::Error::other)?;
+        }
+        writer.finish().map_err(io::Error::other)
+    }
+
+    /// One PNG per frame in `dir`, which is created if needed.
+    pub fn write_pngs(&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        for (i, frame) in self.frames.iter().enumerate() {
+            let file = File::create(dir.join(format!("frame-{:04}.png", i + 1)))?;
+            let mut writer = self
+                .png_encoder(BufWriter::new(file))
+                .write_header()
+                .map_err(io::Error::other)?;
+            writer
+                .write_image_data(&self.pixels(frame))
+                .map_err(io::Error::other)?;
+            writer.finish().map_err(io::Error::other)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorder() -> Recorder {
+        let mut recorder = Recorder::new(2, 1)
+            .cell_size(2)
+            .palette(&[Rgb(0, 0, 0), Rgb(255, 0, 0)]);
+        let grid = Grid::from_fn(2, 1, |(x, _)| x);
+        recorder.record(&grid, |_, x| *x as u8);
+        recorder.record(&grid, |_, x| 1 - *x as u8);
+        recorder
+    }
+
+    #[test]
+    fn test_pixels() {
+        let recorder = recorder();
+        assert_eq!(recorder.frames(), 2);
+        assert_eq!(recorder.pixel_size(), (4, 2));
+        assert_eq!(
+            recorder.pixels(&recorder.frames[0]),
+            [0, 0, 1, 1, 0, 0, 1, 1]
+        );
+    }
+
+    #[test]
+    fn test_save() {
+        let recorder = recorder();
+
+        let mut gif = vec![];
+        recorder.write_gif(&mut gif).unwrap();
+        let mut decoder = gif::DecodeOptions::new().read_info(&gif[..]).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (4, 2));
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 10);
+            frames += 1;
+        }
+        assert_eq!(frames, 2);
+
+        let mut apng = vec![];
+        recorder.write_apng(&mut apng).unwrap();
+        let reader = png::Decoder::new(&apng[..]).read_info().unwrap();
+        let control = reader.info().animation_control().unwrap();
+        assert_eq!(control.num_frames, 2);
+
+        let dir = tempfile::tempdir().unwrap();
+        let to = Recording {
+            path: dir.path().join("frames"),
+            cell_size: Some(3),
+        };
+        recorder.save(&to).unwrap();
+        let png = fs::read(to.path.join("frame-0002.png")).unwrap();
+        let reader = png::Decoder::new(&png[..]).read_info().unwrap();
+        assert_eq!((reader.info().width, reader.info().height), (6, 3));
+        assert!(!to.path.join("frame-0003.png").exists());
+    }
+}
@@ -0,0 +1,241 @@
+//! Points, vectors and compass directions. `y` grows downwards like the rows
+//! of an input, so [`Direction::North`] points up the page.
+//!
+//! `Point<usize>` addresses grid cells, `Point<i64>` is for unbounded planes
+//! and anything that may step off the grid.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// The difference between two points.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vector<T> {
+    pub x: T,
+    pub y: T,
+}
+
+// works for unsigned types too, unlike `(a - b).abs()`
+fn abs_diff<T: Copy + Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point<T> {
+    /// Taxicab distance, the number of orthogonal steps between the points.
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+}
+
+impl Point<usize> {
+    pub fn signed(self) -> Point<i64> {
+        Point::new(self.x as i64, self.y as i64)
+    }
+
+    /// One step towards `dir`, `None` when that leaves the `width` by `height` area.
+    pub fn step(self, dir: Direction, width: usize, height: usize) -> Option<Self> {
+        let v = dir.vector::<isize>();
+        let x = self.x.checked_add_signed(v.x).filter(|x| *x < width)?;
+        let y = self.y.checked_add_signed(v.y).filter(|y| *y < height)?;
+        Some(Self::new(x, y))
+    }
+}
+
+impl Point<i64> {
+    /// `None` left of or above the origin.
+    pub fn unsigned(self) -> Option<Point<usize>> {
+        Some(Point::new(self.x.try_into().ok()?, self.y.try_into().ok()?))
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(p: Point<T>) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl<T: Add<Output = T>> Add<Vector<T>> for Point<T> {
+    type Output = Self;
+
+    fn add(self, v: Vector<T>) -> Self {
+        Self::new(self.x + v.x, self.y + v.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign<Vector<T>> for Point<T> {
+    fn add_assign(&mut self, v: Vector<T>) {
+        self.x += v.x;
+        self.y += v.y;
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Vector<T>;
+
+    fn sub(self, other: Self) -> Vector<T> {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T> Vector<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Copy + Default + Ord + Add<Output = T> + Sub<Output = T>> Vector<T> {
+    /// Taxicab length.
+    pub fn manhattan(self) -> T {
+        abs_diff(self.x, T::default()) + abs_diff(self.y, T::default())
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vector<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Vector<T> {
+    type Output = Self;
+
+    fn mul(self, n: T) -> Self {
+        Self::new(self.x * n, self.y * n)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vector<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Clockwise from north.
+    pub const ALL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Self::North => Self::West,
+            Self::East => Self::North,
+            Self::South => Self::East,
+            Self::West => Self::South,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Self::North => Self::East,
+            Self::East => Self::South,
+            Self::South => Self::West,
+            Self::West => Self::North,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn_left().turn_left()
+    }
+
+    /// A single step, in any signed type.
+    pub fn vector<T: From<i8>>(self) -> Vector<T> {
+        let (x, y) = match self {
+            Self::North => (0, -1),
+            Self::East => (1, 0),
+            Self::South => (0, 1),
+            Self::West => (-1, 0),
+        };
+        Vector::new(T::from(x), T::from(y))
+    }
+
+    /// The way it points as drawn on a map.
+    pub fn arrow(self) -> char {
+        match self {
+            Self::North => '^',
+            Self::East => '>',
+            Self::South => 'v',
+            Self::West => '<',
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_direction() {
+        use Direction::*;
+        assert_eq!(North.turn_right(), East);
+        assert_eq!(North.turn_left(), West);
+        assert_eq!(East.opposite(), West);
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(
+                dir.vector::<i64>() + dir.opposite().vector(),
+                Vector::default()
+            );
+        }
+        assert_eq!(South.vector::<i32>(), Vector::new(0, 1));
+        assert_eq!(Direction::ALL.map(Direction::arrow), ['^', '>', 'v', '<']);
+    }
+
+    #[test]
+    fn test_point() {
+        let a = Point::new(1i64, 2);
+        let b = a + Direction::West.vector() * 3;
+        assert_eq!(b, Point::new(-2, 2));
+        assert_eq!(a - b, Vector::new(3, 0));
+        assert_eq!(a.manhattan(b), 3);
+        assert_eq!((b - a).manhattan(), 3);
+        assert_eq!(-(a - b), b - a);
+        assert_eq!(b.unsigned(), None);
+        assert_eq!(a.unsigned(), Some(Point::new(1, 2)));
+
+        let mut c = Point::new(2usize, 7);
+        assert_eq!(c.manhattan(Point::new(5, 3)), 7);
+        assert_eq!(c.signed(), Point::new(2, 7));
+        c += Vector::new(1, 1);
+        assert_eq!(<(usize, usize)>::from(c), (3, 8));
+    }
+
+    #[test]
+    fn test_step() {
+        let p = Point::new(0usize, 1);
+        assert_eq!(p.step(Direction::North, 2, 2), Some(Point::new(0, 0)));
+        assert_eq!(p.step(Direction::East, 2, 2), Some(Point::new(1, 1)));
+        assert_eq!(p.step(Direction::South, 2, 2), None);
+        assert_eq!(p.step(Direction::West, 2, 2), None);
+    }
+}
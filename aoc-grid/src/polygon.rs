@@ -0,0 +1,289 @@
+//! Polygons with their corners on the integer lattice, like a loop drawn
+//! through grid cells or dug out by a sequence of moves.
+//!
+//! Coordinates are `i64`, anything multiplied goes through `i128` so areas
+//! can't overflow, and walking moves is checked.
+
+use crate::geometry::{Direction, Point};
+
+/// The edges run from each vertex to the next and from the last back to the first.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Polygon {
+    vertices: Vec<Point<i64>>,
+}
+
+// `b - a`, which may not fit an `i64`
+fn delta(a: Point<i64>, b: Point<i64>) -> (i128, i128) {
+    (b.x as i128 - a.x as i128, b.y as i128 - a.y as i128)
+}
+
+// positive when `c` is clockwise from `b` as seen from `a`, with `y` down
+fn cross(a: Point<i64>, b: Point<i64>, c: Point<i64>) -> i128 {
+    let (ab, ac) = (delta(a, b), delta(a, c));
+    ab.0 * ac.1 - ab.1 * ac.0
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// whether `p` lies on the segment from `a` to `b`, ends included
+fn on_segment(p: Point<i64>, a: Point<i64>, b: Point<i64>) -> bool {
+    cross(a, b, p) == 0
+        && a.x.min(b.x) <= p.x
+        && p.x <= a.x.max(b.x)
+        && a.y.min(b.y) <= p.y
+        && p.y <= a.y.max(b.y)
+}
+
+// whether the path `a`, `b`, `c` turns straight back on itself at `b`
+fn turns_back(a: Point<i64>, b: Point<i64>, c: Point<i64>) -> bool {
+    let (ab, bc) = (delta(a, b), delta(b, c));
+    cross(a, b, c) == 0 && ab.0 * bc.0 + ab.1 * bc.1 < 0
+}
+
+fn segments_touch(a: Point<i64>, b: Point<i64>, c: Point<i64>, d: Point<i64>) -> bool {
+    let (d1, d2) = (cross(a, b, c).signum(), cross(a, b, d).signum());
+    let (d3, d4) = (cross(c, d, a).signum(), cross(c, d, b).signum());
+    (d1 * d2 < 0 && d3 * d4 < 0)
+        || on_segment(c, a, b)
+        || on_segment(d, a, b)
+        || on_segment(a, c, d)
+        || on_segment(b, c, d)
+}
+
+impl Polygon {
+    /// A closing vertex equal to the first one is dropped.
+    pub fn new(mut vertices: Vec<Point<i64>>) -> Self {
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        Self { vertices }
+    }
+
+    /// The path walked from `start`, turtle style, going `n` steps towards each direction in turn.
+    /// `None` when it leaves what an `i64` can hold.
+    pub fn from_moves(
+        start: Point<i64>,
+        moves: impl IntoIterator<Item = (Direction, i64)>,
+    ) -> Option<Self> {
+        let mut pos = start;
+        let mut vertices = vec![start];
+        for (dir, n) in moves {
+            let step = dir.vector::<i64>();
+            pos = Point::new(
+                pos.x.checked_add(step.x.checked_mul(n)?)?,
+                pos.y.checked_add(step.y.checked_mul(n)?)?,
+            );
+            vertices.push(pos);
+        }
+        Some(Self::new(vertices))
+    }
+
+    pub fn vertices(&self) -> &[Point<i64>] {
+        &self.vertices
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Point<i64>, Point<i64>)> + '_ {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices.iter().copied().zip(next.copied())
+    }
+
+    /// Twice the area by the shoelace formula, which keeps it an integer. Positive
+    /// when the vertices go clockwise as drawn with `y` down, negative otherwise.
+    pub fn signed_double_area(&self) -> i128 {
+        self.edges()
+            .map(|(a, b)| a.x as i128 * b.y as i128 - b.x as i128 * a.y as i128)
+            .sum()
+    }
+
+    pub fn double_area(&self) -> i128 {
+        self.signed_double_area().abs()
+    }
+
+    /// The length of the edges.
+    pub fn perimeter(&self) -> f64 {
+        self.edges()
+            .map(|(a, b)| {
+                let (dx, dy) = delta(a, b);
+                (dx as f64).hypot(dy as f64)
+            })
+            .sum()
+    }
+
+    /// The lattice points on the edges, the vertices included.
+    pub fn boundary_points(&self) -> i128 {
+        self.edges()
+            .map(|(a, b)| {
+                let (dx, dy) = delta(a, b);
+                gcd(dx, dy)
+            })
+            .sum()
+    }
+
+    /// The lattice points strictly inside, by Pick's theorem `A = I + B / 2 - 1`.
+    /// Only meaningful for a polygon that doesn't intersect itself.
+    pub fn interior_points(&self) -> i128 {
+        (self.double_area() - self.boundary_points() + 2) / 2
+    }
+
+    pub fn on_boundary(&self, p: Point<i64>) -> bool {
+        self.edges().any(|(a, b)| on_segment(p, a, b))
+    }
+
+    /// Whether `p` lies strictly inside, by counting the edges a ray from it crosses.
+    pub fn contains(&self, p: Point<i64>) -> bool {
+        if self.on_boundary(p) {
+            return false;
+        }
+        // the ray goes right, an edge counts when it has one end below the ray
+        // and one on or above it, which counts a vertex on the ray only once
+        self.edges()
+            .filter(|&(a, b)| (a.y > p.y) != (b.y > p.y))
+            .filter(|&(a, b)| {
+                let (a, b) = if a.y < b.y { (a, b) } else { (b, a) };
+                // `p` is left of the edge going down
+                cross(a, b, p) < 0
+            })
+            .count()
+            % 2
+            == 1
+    }
+
+    /// Whether any two edges meet anywhere but at the vertex they share,
+    /// including an edge doubling back over the one before it.
+    pub fn self_intersects(&self) -> bool {
+        let edges = self.edges().collect::<Vec<_>>();
+        let n = edges.len();
+        for i in 0..n {
+            for j in i + 1..n {
+                let (a, b) = edges[i];
+                let (c, d) = edges[j];
+                // neighbours share a vertex, and only overlap if one turns straight back
+                let touch = if j == i + 1 {
+                    turns_back(a, b, d)
+                } else if i == 0 && j == n - 1 {
+                    turns_back(c, a, b)
+                } else {
+                    segments_touch(a, b, c, d)
+                };
+                if touch {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(vertices: &[(i64, i64)]) -> Polygon {
+        Polygon::new(vertices.iter().map(|&p| p.into()).collect())
+    }
+
+    #[test]
+    fn test_area() {
+        let square = polygon(&[(0, 0), (4, 0), (4, 4), (0, 4)]);
+        assert_eq!(square.signed_double_area(), 32);
+        assert_eq!(square.perimeter(), 16.0);
+        assert_eq!(square.boundary_points(), 16);
+        assert_eq!(square.interior_points(), 9);
+
+        let triangle = polygon(&[(0, 0), (0, 3), (4, 0), (0, 0)]);
+        assert_eq!(triangle.vertices().len(), 3);
+        assert_eq!(triangle.signed_double_area(), -12);
+        assert_eq!(triangle.perimeter(), 12.0);
+        assert_eq!(triangle.boundary_points(), 8);
+        assert_eq!(triangle.interior_points(), 3);
+
+        // far past what an i64 area could hold
+        let huge = polygon(&[(0, 0), (i64::MAX, 0), (i64::MAX, i64::MAX), (0, i64::MAX)]);
+        assert_eq!(huge.double_area(), 2 * (i64::MAX as i128).pow(2));
+        assert_eq!(huge.boundary_points(), 4 * i64::MAX as i128);
+        assert!(huge.contains(Point::new(1, 1)));
+        let wide = polygon(&[(i64::MIN, 0), (i64::MAX, 0), (0, 1)]);
+        assert_eq!(wide.double_area(), u64::MAX as i128);
+        assert!(!wide.self_intersects());
+    }
+
+    #[test]
+    fn test_from_moves() {
+        use Direction::*;
+        // the day 18 example
+        let moves = [
+            (East, 6),
+            (South, 5),
+            (West, 2),
+            (South, 2),
+            (East, 2),
+            (South, 2),
+            (West, 5),
+            (North, 2),
+            (West, 1),
+            (North, 2),
+            (East, 2),
+            (North, 3),
+            (West, 2),
+            (North, 2),
+        ];
+        let lagoon = Polygon::from_moves(Point::new(0, 0), moves).unwrap();
+        assert_eq!(lagoon.vertices().len(), 14);
+        assert_eq!(lagoon.boundary_points(), 38);
+        assert_eq!(lagoon.interior_points() + lagoon.boundary_points(), 62);
+        assert!(!lagoon.self_intersects());
+
+        let far = [(East, i64::MAX), (East, 1)];
+        assert_eq!(Polygon::from_moves(Point::new(0, 0), far), None);
+        let far = [(North, i64::MAX), (South, i64::MAX)];
+        assert!(Polygon::from_moves(Point::new(0, -1), far).is_some());
+    }
+
+    #[test]
+    fn test_contains() {
+        // a U shape, open at the top
+        let u = polygon(&[
+            (0, 0),
+            (1, 0),
+            (1, 2),
+            (3, 2),
+            (3, 0),
+            (4, 0),
+            (4, 3),
+            (0, 3),
+        ]);
+        assert!(!u.contains(Point::new(2, 1)));
+        assert!(u.on_boundary(Point::new(2, 2)));
+        assert!(!u.contains(Point::new(2, 2)));
+        assert!(!u.contains(Point::new(5, 1)));
+        assert!(!u.contains(Point::new(-1, 0)));
+        let square = polygon(&[(0, 0), (4, 0), (4, 4), (0, 4)]);
+        let inside = (-1..6)
+            .flat_map(|y| (-1..6).map(move |x| Point::new(x, y)))
+            .filter(|p| square.contains(*p))
+            .count();
+        assert_eq!(inside as i128, square.interior_points());
+        let triangle = polygon(&[(0, 0), (0, 3), (4, 0)]);
+        assert!(triangle.contains(Point::new(1, 1)));
+        assert!(!triangle.contains(Point::new(2, 2)));
+        assert!(triangle.on_boundary(Point::new(0, 2)));
+    }
+
+    #[test]
+    fn test_self_intersects() {
+        assert!(polygon(&[(0, 0), (2, 2), (2, 0), (0, 2)]).self_intersects());
+        assert!(!polygon(&[(0, 0), (2, 0), (2, 2), (0, 2)]).self_intersects());
+        // touching itself at a vertex
+        assert!(polygon(&[(0, 0), (2, 0), (2, 2), (1, 0), (0, 2)]).self_intersects());
+        // doubling back along an edge
+        assert!(polygon(&[(0, 0), (3, 0), (1, 0), (1, 2)]).self_intersects());
+        // straight through a vertex in the middle of an edge is fine
+        assert!(!polygon(&[(0, 0), (1, 0), (2, 0), (2, 2)]).self_intersects());
+    }
+}
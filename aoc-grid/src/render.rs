@@ -0,0 +1,194 @@
+//! Grids drawn for the terminal, to look at what a solution computed: every
+//! cell is a single character with its own colours, set with 24-bit ANSI
+//! escape codes.
+
+use std::fmt::{Display, Write};
+
+use crate::{Grid, Pos};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// From six hex digits, as in `70c710`.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        Some(Self(channel(0)?, channel(2)?, channel(4)?))
+    }
+
+    /// Part of the way from `self` to `other`, `t` going from 0 to 1.
+    pub fn mix(self, other: Self, t: f64) -> Self {
+        let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Self(
+            channel(self.0, other.0),
+            channel(self.1, other.1),
+            channel(self.2, other.2),
+        )
+    }
+}
+
+/// As `#rrggbb`, the way CSS and SVG take it.
+impl Display for Rgb {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+// cold to hot, evenly spaced
+const HEAT: [Rgb; 4] = [
+    Rgb(20, 30, 90),
+    Rgb(30, 140, 140),
+    Rgb(230, 200, 50),
+    Rgb(200, 30, 30),
+];
+
+/// The colour of `value` on a heat map going from `min` to `max`, values
+/// outside are clamped.
+pub fn heat(value: f64, min: f64, max: f64) -> Rgb {
+    let t = if max > min {
+        ((value - min) / (max - min)).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let scaled = t * (HEAT.len() - 1) as f64;
+    let i = (scaled as usize).min(HEAT.len() - 2);
+    HEAT[i].mix(HEAT[i + 1], scaled - i as f64)
+}
+
+/// A character and how to draw it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub glyph: char,
+    pub fg: Option<Rgb>,
+    pub bg: Option<Rgb>,
+    pub bold: bool,
+    /// Drawn in reverse video, which stands out whatever the colours.
+    pub highlight: bool,
+}
+
+impl Cell {
+    /// In the terminal's own colours.
+    pub fn new(glyph: char) -> Self {
+        Self {
+            glyph,
+            fg: None,
+            bg: None,
+            bold: false,
+            highlight: false,
+        }
+    }
+
+    pub fn fg(self, color: Rgb) -> Self {
+        Self {
+            fg: Some(color),
+            ..self
+        }
+    }
+
+    pub fn bg(self, color: Rgb) -> Self {
+        Self {
+            bg: Some(color),
+            ..self
+        }
+    }
+
+    pub fn bold(self) -> Self {
+        Self { bold: true, ..self }
+    }
+
+    pub fn highlight(self) -> Self {
+        Self {
+            highlight: true,
+            ..self
+        }
+    }
+
+    fn write(&self, out: &mut String, ansi: bool) {
+        let mut codes = vec![];
+        if ansi {
+            if self.bold {
+                codes.push("1".to_string());
+            }
+            if self.highlight {
+                codes.push("7".to_string());
+            }
+            if let Some(Rgb(r, g, b)) = self.fg {
+                codes.push(format!("38;2;{};{};{}", r, g, b));
+            }
+            if let Some(Rgb(r, g, b)) = self.bg {
+                codes.push(format!("48;2;{};{};{}", r, g, b));
+            }
+        }
+        if codes.is_empty() {
+            out.push(self.glyph);
+        } else {
+            let _ = write!(out, "\x1b[{}m{}\x1b[0m", codes.join(";"), self.glyph);
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// One line per row with `cell` deciding how each one looks. Without `ansi`
+    /// only the glyphs are drawn, for output that isn't going to a terminal.
+    pub fn render(&self, ansi: bool, mut cell: impl FnMut(Pos, &T) -> Cell) -> String {
+        let mut out = String::new();
+        for (y, row) in self.rows().enumerate() {
+            for (x, value) in row.iter().enumerate() {
+                cell((x, y), value).write(&mut out, ansi);
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heat() {
+        assert_eq!(heat(0.0, 0.0, 9.0), HEAT[0]);
+        assert_eq!(heat(9.0, 0.0, 9.0), HEAT[3]);
+        assert_eq!(heat(3.0, 0.0, 9.0), HEAT[1]);
+        assert_eq!(heat(-5.0, 0.0, 9.0), HEAT[0]);
+        assert_eq!(heat(20.0, 0.0, 9.0), HEAT[3]);
+        assert_eq!(heat(1.0, 1.0, 1.0), HEAT[0]);
+        assert_eq!(
+            Rgb(0, 100, 200).mix(Rgb(100, 100, 0), 0.5),
+            Rgb(50, 100, 100)
+        );
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(Rgb::from_hex("70c710"), Some(Rgb(0x70, 0xc7, 0x10)));
+        assert_eq!(Rgb(0x70, 0xc7, 0x10).to_string(), "#70c710");
+        assert_eq!(Rgb::from_hex("70c71"), None);
+        assert_eq!(Rgb::from_hex("+0c710"), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let grid = Grid::from_fn(2, 2, |(x, y)| x + 2 * y);
+        let plain = grid.render(false, |_, n| Cell::new(char::from(b'0' + *n as u8)).bold());
+        assert_eq!(plain, "01\n23\n");
+
+        let ansi = grid.render(true, |(x, y), n| {
+            let cell = Cell::new(char::from(b'0' + *n as u8));
+            match (x, y) {
+                (0, 0) => cell,
+                (1, 0) => cell.fg(Rgb(1, 2, 3)),
+                (0, 1) => cell.bg(Rgb(4, 5, 6)).bold(),
+                _ => cell.highlight(),
+            }
+        });
+        assert_eq!(
+            ansi,
+            "0\x1b[38;2;1;2;3m1\x1b[0m\n\x1b[1;48;2;4;5;6m2\x1b[0m\x1b[7m3\x1b[0m\n"
+        );
+    }
+}
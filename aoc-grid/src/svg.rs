@@ -0,0 +1,133 @@
+//! Shapes on the plane drawn as SVG, scaled to fit a fixed size whatever their
+//! coordinates, so huge ones can be looked at too.
+
+use std::fmt::Write;
+
+use crate::{geometry::Point, polygon::Polygon, render::Rgb};
+
+// the space left around the drawing, in pixels
+const MARGIN: f64 = 10.0;
+
+/// Maps plane coordinates onto an image, keeping the proportions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    min: Point<i64>,
+    scale: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Viewport {
+    /// The smallest box around `points`, scaled so its longer side is `size` pixels.
+    pub fn fit(points: &[Point<i64>], size: f64) -> Self {
+        let min_x = points.iter().map(|p| p.x).min().unwrap_or(0);
+        let max_x = points.iter().map(|p| p.x).max().unwrap_or(0);
+        let min_y = points.iter().map(|p| p.y).min().unwrap_or(0);
+        let max_y = points.iter().map(|p| p.y).max().unwrap_or(0);
+        // the difference may not fit an `i64`
+        let (dx, dy) = (max_x as f64 - min_x as f64, max_y as f64 - min_y as f64);
+        let scale = size / dx.max(dy).max(1.0);
+        Self {
+            min: Point::new(min_x, min_y),
+            scale,
+            width: dx * scale + 2.0 * MARGIN,
+            height: dy * scale + 2.0 * MARGIN,
+        }
+    }
+
+    /// Where `p` ends up in the image.
+    pub fn map(&self, p: Point<i64>) -> (f64, f64) {
+        (
+            (p.x as f64 - self.min.x as f64) * self.scale + MARGIN,
+            (p.y as f64 - self.min.y as f64) * self.scale + MARGIN,
+        )
+    }
+}
+
+/// `polygon` filled with `fill`, with every edge drawn over it in the colour
+/// `stroke` gives for its index, edge `i` running from vertex `i` to the next.
+/// The longer side of the image is `size` pixels, not counting a small margin.
+pub fn polygon(
+    polygon: &Polygon,
+    size: f64,
+    fill: Rgb,
+    mut stroke: impl FnMut(usize) -> Rgb,
+) -> String {
+    let vertices = polygon.vertices();
+    let view = Viewport::fit(vertices, size);
+    // thin enough to tell edges apart, thick enough to see the colours
+    let width = (size / 250.0).max(1.0);
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w:.0}" height="{h:.0}" viewBox="0 0 {w:.2} {h:.2}">"#,
+        w = view.width,
+        h = view.height
+    );
+    let path = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, &p)| {
+            let (x, y) = view.map(p);
+            format!("{}{:.2} {:.2}", if i == 0 { 'M' } else { 'L' }, x, y)
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let _ = writeln!(
+        svg,
+        r#"  <path d="{} Z" fill="{}" fill-opacity="0.5" stroke="none"/>"#,
+        path, fill
+    );
+    let next = vertices.iter().cycle().skip(1);
+    for (i, (&a, &b)) in vertices.iter().zip(next).enumerate() {
+        let ((x1, y1), (x2, y2)) = (view.map(a), view.map(b));
+        let _ = writeln!(
+            svg,
+            r#"  <line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}" stroke="{}" stroke-width="{:.2}" stroke-linecap="square"/>"#,
+            x1,
+            y1,
+            x2,
+            y2,
+            stroke(i),
+            width
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_viewport() {
+        let view = Viewport::fit(&[Point::new(-100, 0), Point::new(300, 200)], 100.0);
+        assert_eq!((view.width, view.height), (120.0, 70.0));
+        assert_eq!(view.map(Point::new(-100, 0)), (10.0, 10.0));
+        assert_eq!(view.map(Point::new(300, 200)), (110.0, 60.0));
+        // a single point doesn't divide by zero
+        let view = Viewport::fit(&[Point::new(5, 5)], 100.0);
+        assert_eq!(view.map(Point::new(5, 5)), (10.0, 10.0));
+    }
+
+    #[test]
+    fn test_polygon() {
+        let square = Polygon::new(vec![
+            Point::new(0, 0),
+            Point::new(i64::MAX / 2, 0),
+            Point::new(i64::MAX / 2, i64::MAX / 2),
+            Point::new(0, i64::MAX / 2),
+        ]);
+        let colors = [Rgb(255, 0, 0), Rgb(0, 255, 0), Rgb(0, 0, 255), Rgb(0, 0, 0)];
+        let svg = polygon(&square, 500.0, Rgb(1, 2, 3), |i| colors[i]);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains(r##"width="520" height="520""##));
+        assert!(svg.contains(r#"d="M10.00 10.00 L510.00 10.00 L510.00 510.00 L10.00 510.00 Z""#));
+        assert!(svg.contains(r##"fill="#010203""##));
+        assert_eq!(svg.matches("<line").count(), 4);
+        assert!(svg.contains(r##"x1="10.00" y1="510.00" x2="10.00" y2="10.00" stroke="#000000""##));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
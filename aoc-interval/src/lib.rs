@@ -0,0 +1,395 @@
+//! Sets of integers kept as the ranges they are made of, for puzzles where
+//! there are far too many values to handle one at a time.
+//!
+//! All intervals are half-open: `start` is in, `end` is not.
+
+use std::{
+    fmt::Debug,
+    ops::{Add, Sub},
+};
+
+/// What intervals can be made of, in practice the integer types.
+pub trait Value: Copy + Ord + Default + Debug + Add<Output = Self> + Sub<Output = Self> {}
+
+impl<T: Copy + Ord + Default + Debug + Add<Output = T> + Sub<Output = T>> Value for T {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Value> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    pub fn with_len(start: T, len: T) -> Self {
+        Self::new(start, start + len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::default()
+        } else {
+            self.end - self.start
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// The values in both, `None` when there are none.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let overlap = Self::new(self.start.max(other.start), self.end.min(other.end));
+        (!overlap.is_empty()).then_some(overlap)
+    }
+}
+
+/// Disjoint intervals in order, with touching ones merged so every set has a
+/// single representation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { intervals: vec![] }
+    }
+}
+
+impl<T: Value> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        // everything from the first interval reaching `interval` to the last one it reaches
+        let from = self.intervals.partition_point(|i| i.end < interval.start);
+        let to = self.intervals.partition_point(|i| i.start <= interval.end);
+        let merged = self.intervals[from..to].iter().fold(interval, |merged, i| {
+            Interval::new(merged.start.min(i.start), merged.end.max(i.end))
+        });
+        self.intervals.splice(from..to, [merged]);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals.iter().copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of values in the set.
+    pub fn len(&self) -> T {
+        self.iter().fold(T::default(), |len, i| len + i.len())
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals.get(i).is_some_and(|i| i.contains(value))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.iter().chain(other.iter()).collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (a, b) = (&self.intervals, &other.intervals);
+        let (mut i, mut j) = (0, 0);
+        let mut intervals = vec![];
+        while i < a.len() && j < b.len() {
+            intervals.extend(a[i].intersection(&b[j]));
+            // whichever ends first can't overlap anything further on
+            if a[i].end < b[j].end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { intervals }
+    }
+
+    /// The values in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        for interval in self.iter() {
+            let mut start = interval.start;
+            for cut in other.iter() {
+                if cut.start >= interval.end {
+                    break;
+                }
+                if cut.end <= start {
+                    continue;
+                }
+                if cut.start > start {
+                    intervals.push(Interval::new(start, cut.start));
+                }
+                start = cut.end;
+            }
+            if start < interval.end {
+                intervals.push(Interval::new(start, interval.end));
+            }
+        }
+        Self { intervals }
+    }
+
+    /// The values below `at`, and those from `at` up.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let mut below = vec![];
+        let mut above = vec![];
+        for interval in self.iter() {
+            if interval.end <= at {
+                below.push(interval);
+            } else if interval.start >= at {
+                above.push(interval);
+            } else {
+                below.push(Interval::new(interval.start, at));
+                above.push(Interval::new(at, interval.end));
+            }
+        }
+        (Self { intervals: below }, Self { intervals: above })
+    }
+}
+
+impl<T: Value> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Self::from_iter([interval])
+    }
+}
+
+impl<T: Value> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut intervals = iter
+            .into_iter()
+            .filter(|i| !i.is_empty())
+            .collect::<Vec<_>>();
+        intervals.sort();
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        Self { intervals: merged }
+    }
+}
+
+/// Moves the values in a few source intervals to where each one's target
+/// starts, keeping their order. Values outside every source stay as they are.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeMap<T> {
+    // by source, which don't overlap
+    pieces: Vec<(Interval<T>, T)>,
+}
+
+impl<T> Default for RangeMap<T> {
+    fn default() -> Self {
+        Self { pieces: vec![] }
+    }
+}
+
+impl<T: Value> RangeMap<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps `source` onto the interval as long as it starting at `target`.
+    /// Panics when `source` overlaps one inserted before.
+    pub fn insert(&mut self, source: Interval<T>, target: T) {
+        if let Err(other) = self.try_insert(source, target) {
+            panic!("sources overlap, {:?} and {:?}", other, source);
+        }
+    }
+
+    /// Like [`RangeMap::insert`], but leaves the map as it was and returns the
+    /// source inserted before that `source` overlaps.
+    pub fn try_insert(&mut self, source: Interval<T>, target: T) -> Result<(), Interval<T>> {
+        let at = self.pieces.partition_point(|(s, _)| s.end <= source.start);
+        if let Some((other, _)) = self.pieces.get(at) {
+            if other.intersection(&source).is_some() {
+                return Err(*other);
+            }
+        }
+        self.pieces.insert(at, (source, target));
+        Ok(())
+    }
+
+    pub fn get(&self, value: T) -> T {
+        let at = self.pieces.partition_point(|(s, _)| s.end <= value);
+        match self.pieces.get(at) {
+            Some((source, target)) if source.contains(value) => *target + (value - source.start),
+            _ => value,
+        }
+    }
+
+    /// Where every value in `set` ends up.
+    pub fn map(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut image = vec![];
+        for (source, target) in &self.pieces {
+            let moved = set.intersection(&IntervalSet::from(*source));
+            image.extend(moved.iter().map(|i| {
+                Interval::new(
+                    *target + (i.start - source.start),
+                    *target + (i.end - source.start),
+                )
+            }));
+        }
+        let sources = self.pieces.iter().map(|(s, _)| *s).collect();
+        image.extend(set.difference(&sources).iter());
+        image.into_iter().collect()
+    }
+}
+
+impl<T: Value> FromIterator<(Interval<T>, T)> for RangeMap<T> {
+    fn from_iter<I: IntoIterator<Item = (Interval<T>, T)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (source, target) in iter {
+            map.insert(source, target);
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+        intervals
+            .iter()
+            .map(|&(s, e)| Interval::new(s, e))
+            .collect()
+    }
+
+    fn pairs(set: &IntervalSet<i32>) -> Vec<(i32, i32)> {
+        set.iter().map(|i| (i.start, i.end)).collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let a = Interval::with_len(3, 4);
+        assert_eq!(a, Interval::new(3, 7));
+        assert_eq!(a.len(), 4);
+        assert!(a.contains(3) && !a.contains(7));
+        assert_eq!(
+            a.intersection(&Interval::new(5, 10)),
+            Some(Interval::new(5, 7))
+        );
+        assert_eq!(a.intersection(&Interval::new(7, 10)), None);
+        assert_eq!(Interval::new(5, 2).len(), 0);
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut s = set(&[(0, 2), (10, 12), (5, 6)]);
+        assert_eq!(pairs(&s), [(0, 2), (5, 6), (10, 12)]);
+        s.insert(Interval::new(2, 5));
+        assert_eq!(pairs(&s), [(0, 6), (10, 12)]);
+        s.insert(Interval::new(8, 9));
+        s.insert(Interval::new(7, 7));
+        assert_eq!(pairs(&s), [(0, 6), (8, 9), (10, 12)]);
+        s.insert(Interval::new(-5, 20));
+        assert_eq!(pairs(&s), [(-5, 20)]);
+        assert_eq!(s.len(), 25);
+        assert_eq!(s.min(), Some(-5));
+        assert_eq!(set(&[(0, 3), (1, 2), (3, 4)]), set(&[(0, 4)]));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[(0, 5), (10, 15)]);
+        let b = set(&[(3, 12), (14, 20)]);
+        assert_eq!(pairs(&a.union(&b)), [(0, 20)]);
+        assert_eq!(pairs(&a.intersection(&b)), [(3, 5), (10, 12), (14, 15)]);
+        assert_eq!(pairs(&a.difference(&b)), [(0, 3), (12, 14)]);
+        assert_eq!(pairs(&b.difference(&a)), [(5, 10), (15, 20)]);
+        assert!(a.difference(&a).is_empty());
+        assert!(a.contains(4) && !a.contains(5) && a.contains(10) && !a.contains(20));
+
+        let (below, above) = a.split_at(12);
+        assert_eq!(pairs(&below), [(0, 5), (10, 12)]);
+        assert_eq!(pairs(&above), [(12, 15)]);
+        let (below, above) = a.split_at(0);
+        assert!(below.is_empty());
+        assert_eq!(above, a);
+    }
+
+    #[test]
+    fn test_range_map() {
+        // the first map of the day 5 example, seed to soil
+        let map = [
+            (Interval::with_len(98u64, 2), 50),
+            (Interval::with_len(50, 48), 52),
+        ]
+        .into_iter()
+        .collect::<RangeMap<_>>();
+        assert_eq!(map.get(79), 81);
+        assert_eq!(map.get(98), 50);
+        assert_eq!(map.get(10), 10);
+
+        let seeds =
+            IntervalSet::from_iter([Interval::with_len(79, 14), Interval::with_len(95, 10)]);
+        let soil = map.map(&seeds);
+        let expected = IntervalSet::from_iter([
+            Interval::new(50, 52),
+            Interval::new(81, 95),
+            Interval::new(97, 100),
+            Interval::new(100, 105),
+        ]);
+        assert_eq!(soil, expected);
+        assert_eq!(soil.len(), seeds.len());
+    }
+
+    #[test]
+    fn test_range_map_many() {
+        // every other stretch of ten moved up by a thousand
+        let map = (0..1000u64)
+            .map(|i| (Interval::with_len(20 * i, 10), 20 * i + 1000))
+            .collect::<RangeMap<_>>();
+        assert_eq!(map.get(0), 1000);
+        assert_eq!(map.get(9), 1009);
+        assert_eq!(map.get(10), 10);
+        assert_eq!(map.get(19), 19);
+        assert_eq!(map.get(12_345), 13_345);
+        assert_eq!(map.get(19_989), 20_989);
+        assert_eq!(map.get(19_999), 19_999);
+        assert_eq!(map.get(20_000), 20_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "sources overlap")]
+    fn test_range_map_overlap() {
+        let mut map = RangeMap::new();
+        map.insert(Interval::new(0, 10), 100);
+        map.insert(Interval::new(5, 15), 200);
+    }
+
+    #[test]
+    fn test_range_map_try_insert() {
+        let mut map = RangeMap::new();
+        assert_eq!(map.try_insert(Interval::new(0, 10), 100), Ok(()));
+        assert_eq!(
+            map.try_insert(Interval::new(5, 15), 200),
+            Err(Interval::new(0, 10))
+        );
+        assert_eq!(map.try_insert(Interval::new(10, 15), 200), Ok(()));
+        assert_eq!(map.get(7), 107);
+        assert_eq!(map.get(12), 202);
+    }
+}
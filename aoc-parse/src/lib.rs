@@ -0,0 +1,320 @@
+//! nom combinators for the shapes puzzle inputs keep coming in, to use with
+//! [`parse_all`](aoc_core::parse::parse_all) and friends.
+//!
+//! Lines may end in `\n` or `\r\n` everywhere.
+
+use std::{borrow::Cow, str::FromStr};
+
+use aoc_core::{
+    parse::{convert, Error, IResult},
+    ParseError,
+};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, digit1, line_ending, one_of, space0, space1},
+    combinator::{map_res, opt, recognize},
+    error::context,
+    multi::{many1_count, separated_list1},
+    sequence::{pair, preceded, terminated},
+    Parser,
+};
+
+/// A number without a sign.
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+    context("a number", map_res(digit1, str::parse))(input)
+}
+
+/// A number with an optional `+` or `-` in front.
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+    context(
+        "a number",
+        map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse),
+    )(input)
+}
+
+/// Unsigned numbers on a line, separated by spaces or tabs.
+pub fn unsigned_list<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    separated_list1(space1, unsigned)(input)
+}
+
+/// Signed numbers on a line, separated by spaces or tabs.
+pub fn signed_list<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    separated_list1(space1, signed)(input)
+}
+
+/// The end of a line followed by at least one empty line.
+pub fn blank_line(input: &str) -> IResult<&str, &str> {
+    recognize(pair(line_ending, many1_count(line_ending)))(input)
+}
+
+/// One or more lines, each parsed by `parser`.
+pub fn lines<'a, O>(
+    parser: impl Parser<&'a str, O, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(line_ending, parser)
+}
+
+/// One or more blocks of lines with blank lines between them, each parsed by `parser`.
+pub fn sections<'a, O>(
+    parser: impl Parser<&'a str, O, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(blank_line, parser)
+}
+
+// `literal` in the error when `parser` fails
+fn expecting<'a, O>(
+    literal: &'static str,
+    mut parser: impl Parser<&'a str, O, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O> {
+    move |input| {
+        parser.parse(input).map_err(|err| {
+            err.map(|err| Error {
+                expected: Cow::Owned(format!("{:?}", literal)),
+                ..err
+            })
+        })
+    }
+}
+
+/// `key=value`, as in `{x=787,m=2655}`.
+pub fn field<'a, O>(
+    key: &'static str,
+    value: impl Parser<&'a str, O, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O> {
+    preceded(pair(expecting(key, tag(key)), char('=')), value)
+}
+
+/// `label:` and any spaces before the value, as in `Time:   7  15`.
+pub fn labelled<'a, O>(
+    label: &'static str,
+    value: impl Parser<&'a str, O, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O> {
+    preceded(
+        terminated(expecting(label, tag(label)), pair(char(':'), space0)),
+        value,
+    )
+}
+
+/// A block of single character cells, one row per line, where every row has the
+/// same width. `cell` returns `None` for characters it doesn't accept, reported as
+/// `expected`.
+///
+/// The grid ends at the first line that doesn't start with a cell, anything else
+/// wrong with a row is a failure rather than the end of the grid.
+pub fn grid<'a, T>(
+    expected: &'static str,
+    mut cell: impl FnMut(char) -> Option<T>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Vec<T>>> {
+    move |input| {
+        let mut rows: Vec<Vec<T>> = vec![];
+        let mut rest = input;
+        loop {
+            let line = &rest[..rest.find(['\r', '\n']).unwrap_or(rest.len())];
+            let mut row = vec![];
+            for (i, c) in line.char_indices() {
+                match cell(c) {
+                    Some(value) => row.push(value),
+                    None if i == 0 => break,
+                    None => {
+                        return Err(nom::Err::Failure(Error {
+                            input: &line[i..],
+                            expected: Cow::Borrowed(expected),
+                        }))
+                    }
+                }
+            }
+            if row.is_empty() {
+                if rows.is_empty() {
+                    return Err(nom::Err::Error(Error {
+                        input: rest,
+                        expected: Cow::Borrowed(expected),
+                    }));
+                }
+                return Ok((rest, rows));
+            }
+            if let Some(width) = rows.first().map(Vec::len) {
+                if row.len() != width {
+                    let end = line
+                        .char_indices()
+                        .nth(width)
+                        .map_or(line.len(), |(i, _)| i);
+                    return Err(nom::Err::Failure(Error {
+                        input: &line[end..],
+                        expected: Cow::Owned(format!("a row of {} cells", width)),
+                    }));
+                }
+            }
+            rows.push(row);
+            // the line break is only taken if another row follows it
+            let after = &rest[line.len()..];
+            match line_ending::<_, Error<&str>>(after) {
+                Ok((next, _)) => rest = next,
+                Err(_) => return Ok((after, rows)),
+            }
+            if rest.chars().next().is_none_or(|c| cell(c).is_none()) {
+                return Ok((after, rows));
+            }
+        }
+    }
+}
+
+/// The whole of `lines` parsed as a [`grid`], errors pointing into `input`, of
+/// which `lines` has to be a subslice.
+pub fn parse_grid<'a, T>(
+    day: u8,
+    input: &'a str,
+    lines: &'a str,
+    expected: &'static str,
+    cell: impl FnMut(char) -> Option<T>,
+) -> Result<Vec<Vec<T>>, ParseError> {
+    let (rest, rows) =
+        grid(expected, cell)(lines.trim_end()).map_err(|err| convert(day, input, err))?;
+    // the grid stops before a line that doesn't start with a cell
+    match line_ending::<_, Error<&str>>(rest) {
+        Ok((line, _)) if line.starts_with(['\r', '\n']) => Err(ParseError::new(
+            day,
+            input,
+            line,
+            format!("a row of {} cells", rows[0].len()),
+        )),
+        Ok((line, _)) => Err(ParseError::new(day, input, line, expected)),
+        Err(_) => Ok(rows),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::parse::parse_all;
+    use nom::sequence::{separated_pair, tuple};
+
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(
+            parse_all(0, "0 3  6\t9", unsigned_list),
+            Ok(vec![0u32, 3, 6, 9])
+        );
+        assert_eq!(
+            parse_all(0, "10 -13 +16 -21\n", signed_list),
+            Ok(vec![10i64, -13, 16, -21])
+        );
+        assert_eq!(parse_all(0, "12", signed), Ok(12u8));
+
+        let err = parse_all(0, "1 -2", unsigned_list::<u32>).unwrap_err();
+        assert_eq!(err.column, 3);
+        let err = parse_all(0, "256", unsigned::<u8>).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (1, "a number"));
+        let err = parse_all(0, "-", signed::<i32>).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (2, "a number"));
+    }
+
+    #[test]
+    fn test_sections() {
+        let parser = || sections(lines(unsigned_list::<u32>));
+        let expected = vec![vec![vec![1, 2], vec![3]], vec![vec![4]]];
+        assert_eq!(
+            parse_all(0, "1 2\n3\n\n4\n", parser()),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            parse_all(0, "1 2\r\n3\r\n\r\n4\r\n", parser()),
+            Ok(expected.clone())
+        );
+        assert_eq!(parse_all(0, "1 2\n3\n\n\n4", parser()), Ok(expected));
+
+        let err = parse_all(0, "1\n\nx\n", parser()).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.expected, "end of input");
+    }
+
+    #[test]
+    fn test_fields() {
+        let part = tuple((
+            field("x", unsigned::<u32>),
+            preceded(char(','), field("m", unsigned::<u32>)),
+        ));
+        assert_eq!(parse_all(0, "x=787,m=2655", part), Ok((787, 2655)));
+        let err = parse_all(0, "x=1,a=2", field("x", unsigned::<u32>)).unwrap_err();
+        assert_eq!(err.column, 4);
+        let err = parse_all(0, "m=1", field("x", unsigned::<u32>)).unwrap_err();
+        assert_eq!(err.expected, "\"x\"");
+
+        let races = separated_pair(
+            labelled("Time", unsigned_list::<u32>),
+            line_ending,
+            labelled("Distance", unsigned_list::<u32>),
+        );
+        assert_eq!(
+            parse_all(0, "Time:      7  15\r\nDistance:  9  40\r\n", races),
+            Ok((vec![7, 15], vec![9, 40]))
+        );
+        let err = parse_all(0, "Time 7", labelled("Time", unsigned::<u32>)).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (5, "':'"));
+    }
+
+    #[test]
+    fn test_grid() {
+        let cell = |c: char| matches!(c, '.' | '#').then_some(c == '#');
+        let grids = parse_all(
+            0,
+            "#.\n.#\n\n..\r\n##\r\n.#\r\n",
+            sections(grid("'.' or '#'", cell)),
+        );
+        assert_eq!(
+            grids,
+            Ok(vec![
+                vec![vec![true, false], vec![false, true]],
+                vec![vec![false, false], vec![true, true], vec![false, true]],
+            ])
+        );
+
+        // a grid followed by something else
+        let (rest, rows) = grid("'.' or '#'", cell)("#.#\nend").unwrap();
+        assert_eq!((rest, rows.len()), ("\nend", 1));
+
+        let err = parse_all(0, "#.\n.x", grid("'.' or '#'", cell)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.expected, "'.' or '#'");
+        let err = parse_all(0, "#.\n.", grid("'.' or '#'", cell)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.expected, "a row of 2 cells");
+        let err = parse_all(0, "x", grid("'.' or '#'", cell)).unwrap_err();
+        assert_eq!(err.expected, "'.' or '#'");
+    }
+
+    #[test]
+    fn test_parse_grid() {
+        let digit = |c: char| c.to_digit(10);
+        let input = "12\n34\n";
+        assert_eq!(
+            parse_grid(1, input, input, "a digit", digit),
+            Ok(vec![vec![1, 2], vec![3, 4]])
+        );
+
+        let input = "12\n3x";
+        let err = parse_grid(1, input, input, "a digit", digit).unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 2, "x"));
+
+        let input = "12\n345";
+        let err = parse_grid(1, input, input, "a digit", digit).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.expected, "a row of 2 cells");
+
+        let input = "12\n3";
+        let err = parse_grid(1, input, input, "a digit", digit).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+
+        // a line that isn't part of the grid
+        let input = "12\nx4";
+        let err = parse_grid(1, input, input, "a digit", digit).unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (2, 1, "a digit")
+        );
+        let input = "12\n\n34";
+        let err = parse_grid(1, input, input, "a digit", digit).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "a row of 2 cells");
+    }
+}
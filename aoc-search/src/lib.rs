@@ -0,0 +1,278 @@
+//! Shortest paths over any state that can be hashed, with the edges given by a
+//! closure instead of a prebuilt graph.
+//!
+//! Every search takes any number of start states and a goal predicate, and
+//! returns the cheapest path to the first goal state it settles, or `None` when
+//! no goal is reachable. [`costs`] instead explores everything reachable.
+
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// A path found by a search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    pub cost: C,
+    /// From the start state it began at to the goal, both included.
+    pub states: Vec<S>,
+}
+
+impl<S, C> Path<S, C> {
+    pub fn start(&self) -> &S {
+        self.states.first().expect("a path has at least its start")
+    }
+
+    pub fn goal(&self) -> &S {
+        self.states.last().expect("a path has at least its start")
+    }
+}
+
+/// The states discovered so far, numbered in order so the search can work on indices.
+struct Seen<S> {
+    index: HashMap<S, usize>,
+    states: Vec<S>,
+    // a start is its own parent
+    parent: Vec<usize>,
+}
+
+impl<S: Hash + Eq + Clone> Seen<S> {
+    fn new() -> Self {
+        Self {
+            index: HashMap::new(),
+            states: vec![],
+            parent: vec![],
+        }
+    }
+
+    /// The index of `state` and whether it is new.
+    fn insert(&mut self, state: S, parent: Option<usize>) -> (usize, bool) {
+        match self.index.entry(state) {
+            Entry::Occupied(entry) => (*entry.get(), false),
+            Entry::Vacant(entry) => {
+                let i = self.states.len();
+                self.states.push(entry.key().clone());
+                self.parent.push(parent.unwrap_or(i));
+                entry.insert(i);
+                (i, true)
+            }
+        }
+    }
+
+    fn path<C>(&self, mut i: usize, cost: C) -> Path<S, C> {
+        let mut states = vec![self.states[i].clone()];
+        while self.parent[i] != i {
+            i = self.parent[i];
+            states.push(self.states[i].clone());
+        }
+        states.reverse();
+        Path { cost, states }
+    }
+}
+
+/// Breadth first, for when every step costs the same. The cost is the number of steps.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, usize>>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+{
+    let mut seen = Seen::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let (i, true) = seen.insert(start, None) {
+            queue.push_back((i, 0));
+        }
+    }
+
+    while let Some((i, steps)) = queue.pop_front() {
+        if goal(&seen.states[i]) {
+            return Some(seen.path(i, steps));
+        }
+        for next in neighbors(&seen.states[i]) {
+            if let (j, true) = seen.insert(next, Some(i)) {
+                queue.push_back((j, steps + 1));
+            }
+        }
+    }
+    None
+}
+
+/// Cheapest first, `neighbors` gives every next state with the cost of moving there.
+/// Costs must not be negative.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbors: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Hash + Eq + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, neighbors, |_| C::default(), goal)
+}
+
+/// [`dijkstra`] guided by `heuristic`, an estimate of the remaining cost to a goal.
+/// The path is only the cheapest if the estimate never exceeds the actual cost
+/// and never drops by more than the cost of a step.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Hash + Eq + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let (seen, cost, found) = search(starts, neighbors, heuristic, goal);
+    found.map(|i| seen.path(i, cost[i]))
+}
+
+/// The cheapest cost of getting to every state reachable from the starts, as
+/// [`dijkstra`] would find it. Every state is visited, so the state space has to
+/// be finite.
+pub fn costs<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbors: impl FnMut(&S) -> I,
+) -> HashMap<S, C>
+where
+    S: Hash + Eq + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let (seen, cost, _) = search(starts, neighbors, |_| C::default(), |_| false);
+    seen.states.into_iter().zip(cost).collect()
+}
+
+// settles states cheapest first until one is a goal, and returns that one's index
+// along with everything seen and what it cost to get there
+fn search<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut goal: impl FnMut(&S) -> bool,
+) -> (Seen<S>, Vec<C>, Option<usize>)
+where
+    S: Hash + Eq + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut seen = Seen::new();
+    let mut cost = vec![];
+    let mut done = vec![];
+    // ties go to the state found first, so the search is deterministic
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        if let (i, true) = seen.insert(start, None) {
+            cost.push(C::default());
+            done.push(false);
+            queue.push(Reverse((heuristic(&seen.states[i]), i)));
+        }
+    }
+
+    while let Some(Reverse((_, i))) = queue.pop() {
+        // queued again with a lower cost after this entry, and handled then
+        if done[i] {
+            continue;
+        }
+        done[i] = true;
+        if goal(&seen.states[i]) {
+            return (seen, cost, Some(i));
+        }
+        for (next, step) in neighbors(&seen.states[i]) {
+            let next_cost = cost[i] + step;
+            let (j, new) = seen.insert(next, Some(i));
+            if new {
+                cost.push(next_cost);
+                done.push(false);
+            } else if done[j] || next_cost >= cost[j] {
+                continue;
+            } else {
+                cost[j] = next_cost;
+                seen.parent[j] = i;
+            }
+            queue.push(Reverse((next_cost + heuristic(&seen.states[j]), j)));
+        }
+    }
+    (seen, cost, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a 5x5 room with a wall along x = 2, open at the bottom
+    fn open(&(x, y): &(i32, i32)) -> bool {
+        (0..5).contains(&x) && (0..5).contains(&y) && (x != 2 || y == 4)
+    }
+
+    fn steps(&(x, y): &(i32, i32)) -> Vec<(i32, i32)> {
+        [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)]
+            .into_iter()
+            .filter(open)
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let path = bfs([(0, 0)], steps, |pos| *pos == (4, 0)).unwrap();
+        assert_eq!(path.cost, 12);
+        assert_eq!(path.states.len(), 13);
+        assert_eq!((path.start(), path.goal()), (&(0, 0), &(4, 0)));
+        assert!(path.states.windows(2).all(|w| steps(&w[0]).contains(&w[1])));
+
+        // the closest start wins
+        let path = bfs([(0, 0), (1, 3)], steps, |pos| *pos == (4, 4)).unwrap();
+        assert_eq!(path.start(), &(1, 3));
+        assert_eq!(path.cost, 4);
+
+        assert_eq!(bfs([(0, 0)], steps, |pos| *pos == (2, 0)), None);
+        assert_eq!(bfs([(0, 0)], steps, |_| true).unwrap().states, [(0, 0)]);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // the direct edge costs more than going around
+        let edges = |n: &u8| match n {
+            0 => vec![(1, 10), (2, 1)],
+            2 => vec![(3, 1)],
+            3 => vec![(1, 1)],
+            _ => vec![],
+        };
+        let path = dijkstra([0], edges, |n| *n == 1).unwrap();
+        assert_eq!(path.cost, 3);
+        assert_eq!(path.states, [0, 2, 3, 1]);
+        assert_eq!(dijkstra([1], edges, |n| *n == 0), None);
+        assert_eq!(dijkstra([3, 0], edges, |n| *n == 1).unwrap().cost, 1);
+    }
+
+    #[test]
+    fn test_astar() {
+        let weighted = |pos: &(i32, i32)| steps(pos).into_iter().map(|next| (next, 1));
+        let goal = (4, 0);
+        let distance = |&(x, y): &(i32, i32)| (goal.0 - x).abs() + (goal.1 - y).abs();
+        let path = astar([(0, 0)], weighted, distance, |pos| *pos == goal).unwrap();
+        let expected = dijkstra([(0, 0)], weighted, |pos| *pos == goal).unwrap();
+        assert_eq!(path.cost, expected.cost);
+        assert_eq!(path.cost, 12);
+    }
+
+    #[test]
+    fn test_costs() {
+        let weighted = |pos: &(i32, i32)| steps(pos).into_iter().map(|next| (next, 1));
+        let costs = costs([(0, 0)], weighted);
+        // all but the four wall cells
+        assert_eq!(costs.len(), 21);
+        assert_eq!(costs[&(0, 0)], 0);
+        assert_eq!(costs[&(4, 0)], 12);
+        assert!(!costs.contains_key(&(2, 0)));
+    }
+}
@@ -0,0 +1,243 @@
+use std::{collections::BTreeMap, fmt::Display, fs, io, path::Path};
+
+use aoc_core::Answer;
+use serde::{Deserialize, Serialize};
+
+use crate::runner::Outcome;
+
+pub const DEFAULT_PATH: &str = "answers.toml";
+
+/// Identifies an input by its content, so the same ledger works for every
+/// copy of it and a different account's input never matches by accident.
+pub fn input_id(text: &str) -> String {
+    // FNV-1a, stable across builds unlike `DefaultHasher`
+    let hash = text.bytes().fold(0xcbf29ce484222325u64, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    /// Where the input was read from when it was first recorded, for humans.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+impl Entry {
+    fn part(&self, part: u8) -> &Option<String> {
+        match part {
+            1 => &self.part1,
+            _ => &self.part2,
+        }
+    }
+
+    fn part_mut(&mut self, part: u8) -> &mut Option<String> {
+        match part {
+            1 => &mut self.part1,
+            _ => &mut self.part2,
+        }
+    }
+}
+
+/// Accepted answers per day and input, stored as `[day-NN.<input id>]` tables.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Ledger {
+    days: BTreeMap<String, BTreeMap<String, Entry>>,
+}
+
+fn day_key(day: u8) -> String {
+    format!("day-{:02}", day)
+}
+
+/// What happened when trying to add an answer to the ledger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recorded {
+    New,
+    Unchanged,
+    /// A different answer is already recorded and was kept.
+    Conflict(String),
+}
+
+impl Ledger {
+    /// A missing file is an empty ledger.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => {
+                toml::from_str(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let text = toml::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, text)
+    }
+
+    pub fn get(&self, day: u8, input: &str, part: u8) -> Option<&str> {
+        self.days
+            .get(&day_key(day))?
+            .get(&input_id(input))?
+            .part(part)
+            .as_deref()
+    }
+
+    /// Never overwrites an accepted answer, a refactor that changes it is a bug
+    /// until proven otherwise. Edit the file to correct a wrong entry.
+    pub fn record(
+        &mut self,
+        day: u8,
+        input: &str,
+        source: &str,
+        part: u8,
+        answer: &Answer,
+    ) -> Recorded {
+        let entry = self
+            .days
+            .entry(day_key(day))
+            .or_default()
+            .entry(input_id(input))
+            .or_insert_with(|| Entry {
+                source: Some(source.to_string()),
+                ..Default::default()
+            });
+        let answer = answer.to_string();
+        match entry.part_mut(part) {
+            Some(recorded) if *recorded == answer => Recorded::Unchanged,
+            Some(recorded) => Recorded::Conflict(recorded.clone()),
+            slot => {
+                *slot = Some(answer);
+                Recorded::New
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Mismatch {
+        expected: String,
+    },
+    /// Solved, but there is nothing recorded to compare against.
+    Unrecorded,
+    Unimplemented,
+    NoInput,
+    Fail(String),
+}
+
+impl Verdict {
+    pub fn new(outcome: &Outcome, expected: Option<&str>) -> Self {
+        match (outcome, expected) {
+            (Outcome::Solved { answer, .. }, Some(expected)) if answer == &expected => Self::Pass,
+            (Outcome::Solved { .. }, Some(expected)) => Self::Mismatch {
+                expected: expected.to_string(),
+            },
+            (Outcome::Solved { .. }, None) => Self::Unrecorded,
+            (Outcome::Unimplemented, _) => Self::Unimplemented,
+            (Outcome::MissingInput(_), _) => Self::NoInput,
+            (outcome, _) => Self::Fail(outcome.to_string()),
+        }
+    }
+
+    /// Mismatches and failures make `verify` exit unsuccessfully.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Mismatch { .. } | Self::Fail(_))
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Self::Pass => "pass",
+            Self::Mismatch { .. } => "MISMATCH",
+            Self::Unrecorded => "unrecorded",
+            Self::Unimplemented => "unimplemented",
+            Self::NoInput => "no input",
+            Self::Fail(_) => "FAIL",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn solved(answer: i64) -> Outcome {
+        Outcome::Solved {
+            answer: answer.into(),
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_input_id() {
+        assert_eq!(input_id(""), "cbf29ce484222325");
+        assert_eq!(input_id("a"), "af63dc4c8601ec8c");
+        assert_ne!(input_id("1\n2"), input_id("2\n1"));
+    }
+
+    #[test]
+    fn test_record() {
+        let mut ledger = Ledger::default();
+        assert_eq!(
+            ledger.record(1, "abc", "inputs/day-01.txt", 1, &42.into()),
+            Recorded::New
+        );
+        assert_eq!(
+            ledger.record(1, "abc", "elsewhere", 1, &42.into()),
+            Recorded::Unchanged
+        );
+        assert_eq!(
+            ledger.record(1, "abc", "elsewhere", 1, &43.into()),
+            Recorded::Conflict("42".to_string())
+        );
+        assert_eq!(ledger.get(1, "abc", 1), Some("42"));
+        assert_eq!(ledger.get(1, "abc", 2), None);
+        assert_eq!(ledger.get(1, "other input", 1), None);
+        assert_eq!(ledger.get(2, "abc", 1), None);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("answers.toml");
+        assert_eq!(Ledger::load(&path).unwrap(), Ledger::default());
+
+        let mut ledger = Ledger::default();
+        ledger.record(7, "cards", "inputs/day-07.txt", 2, &"KQJ".into());
+        ledger.save(&path).unwrap();
+
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.contains(&format!("[day-07.{}]", input_id("cards"))));
+        assert!(text.contains("part2 = \"KQJ\""));
+        assert_eq!(Ledger::load(&path).unwrap(), ledger);
+    }
+
+    #[test]
+    fn test_verdict() {
+        assert_eq!(Verdict::new(&solved(1), Some("1")), Verdict::Pass);
+        assert_eq!(
+            Verdict::new(&solved(2), Some("1")),
+            Verdict::Mismatch {
+                expected: "1".to_string()
+            }
+        );
+        assert_eq!(Verdict::new(&solved(2), None), Verdict::Unrecorded);
+        assert_eq!(
+            Verdict::new(&Outcome::Unimplemented, Some("1")),
+            Verdict::Unimplemented
+        );
+        assert!(Verdict::new(&Outcome::Panicked("oops".to_string()), None).is_failure());
+        assert!(!Verdict::new(&Outcome::MissingInput(String::new()), None).is_failure());
+    }
+}
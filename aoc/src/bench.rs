@@ -0,0 +1,331 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use aoc_core::Solution;
+use serde::{Deserialize, Serialize};
+
+use crate::runner::{self, Outcome};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Self::Parse => "parse",
+            Self::Part1 => "part 1",
+            Self::Part2 => "part 2",
+        })
+    }
+}
+
+/// Timings of one phase in nanoseconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min: u64,
+    pub median: u64,
+    pub mean: u64,
+    pub max: u64,
+    pub stddev: u64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "no samples");
+        let mut nanos = samples
+            .iter()
+            .map(|d| d.as_nanos() as f64)
+            .collect::<Vec<_>>();
+        nanos.sort_by(f64::total_cmp);
+
+        let n = nanos.len();
+        let mean = nanos.iter().sum::<f64>() / n as f64;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+        let median = if n % 2 == 0 {
+            (nanos[n / 2 - 1] + nanos[n / 2]) / 2.0
+        } else {
+            nanos[n / 2]
+        };
+
+        Self {
+            iterations: n,
+            min: nanos[0] as u64,
+            median: median as u64,
+            mean: mean as u64,
+            max: nanos[n - 1] as u64,
+            stddev: variance.sqrt() as u64,
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let d = Duration::from_nanos;
+        write!(
+            f,
+            "median {:>10.2?}  mean {:>10.2?} ± {:<10.2?} min {:>10.2?}  max {:>10.2?}",
+            d(self.median),
+            d(self.mean),
+            d(self.stddev),
+            d(self.min),
+            d(self.max)
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub day: u8,
+    pub phase: Phase,
+    pub stats: Stats,
+}
+
+/// What `bench --output` writes and `bench --baseline` reads back.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    pub records: Vec<Record>,
+}
+
+impl Report {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        serde_json::from_str(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let text = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, text + "\n")
+    }
+
+    fn get(&self, day: u8, phase: Phase) -> Option<&Stats> {
+        self.records
+            .iter()
+            .find(|r| r.day == day && r.phase == phase)
+            .map(|r| &r.stats)
+    }
+}
+
+/// Times parsing and both parts `iterations` times each. A phase that can't be
+/// timed (unimplemented part, bad input) is reported as its [`Outcome`] instead.
+pub type Bencher = fn(&str, usize) -> Vec<(Phase, Result<Stats, Outcome>)>;
+
+fn time<T>(iterations: usize, mut f: impl FnMut() -> T) -> Stats {
+    let samples = (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            std::hint::black_box(f());
+            start.elapsed()
+        })
+        .collect::<Vec<_>>();
+    Stats::from_samples(&samples)
+}
+
+pub fn bench<S: Solution>(input: &str, iterations: usize) -> Vec<(Phase, Result<Stats, Outcome>)> {
+    runner::quietly(|| {
+        // parse once up front so a broken input or an unimplemented part
+        // is reported instead of timed
+        let parsed = match runner::parse::<S>(input) {
+            Ok(parsed) => parsed,
+            Err(outcome) => {
+                return [Phase::Parse, Phase::Part1, Phase::Part2]
+                    .into_iter()
+                    .map(|phase| (phase, Err(outcome.clone())))
+                    .collect();
+            }
+        };
+
+        let mut res = vec![(Phase::Parse, Ok(time(iterations, || S::parse(input))))];
+        for (part, phase) in [(1, Phase::Part1), (2, Phase::Part2)] {
+            let stats = runner::solve::<S>(&parsed, part)
+                .map(|_| time(iterations, || runner::solve::<S>(&parsed, part)));
+            res.push((phase, stats));
+        }
+        res
+    })
+}
+
+/// A phase whose median got slower than the baseline by more than the threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub day: u8,
+    pub phase: Phase,
+    pub baseline: u64,
+    pub current: u64,
+}
+
+impl Regression {
+    pub fn ratio(&self) -> f64 {
+        self.current as f64 / self.baseline.max(1) as f64
+    }
+}
+
+impl Display for Regression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {:02} {}: {:.2?} -> {:.2?} ({:+.1}%)",
+            self.day,
+            self.phase,
+            Duration::from_nanos(self.baseline),
+            Duration::from_nanos(self.current),
+            (self.ratio() - 1.0) * 100.0
+        )
+    }
+}
+
+/// Compares medians, phases missing from either report are skipped.
+/// `threshold` is the allowed slowdown in percent.
+pub fn compare(baseline: &Report, current: &Report, threshold: f64) -> Vec<Regression> {
+    current
+        .records
+        .iter()
+        .filter_map(|record| {
+            let base = baseline.get(record.day, record.phase)?;
+            let regression = Regression {
+                day: record.day,
+                phase: record.phase,
+                baseline: base.median,
+                current: record.stats.median,
+            };
+            (regression.ratio() > 1.0 + threshold / 100.0).then_some(regression)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::{Answer, ParseError};
+
+    use super::*;
+
+    struct Example;
+
+    impl Solution for Example {
+        const DAY: u8 = 0;
+
+        type Input<'a> = Vec<u64>;
+
+        fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+            input
+                .split(',')
+                .map(|n| {
+                    n.parse()
+                        .map_err(|_| ParseError::new(Self::DAY, input, n, "a number"))
+                })
+                .collect()
+        }
+
+        fn part1(input: &Self::Input<'_>) -> Answer {
+            input.iter().sum::<u64>().into()
+        }
+
+        fn part2(_input: &Self::Input<'_>) -> Answer {
+            todo!();
+        }
+    }
+
+    fn stats(median: u64) -> Stats {
+        Stats {
+            iterations: 1,
+            min: median,
+            median,
+            mean: median,
+            max: median,
+            stddev: 0,
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let samples = [4, 1, 3, 2].map(Duration::from_nanos);
+        let stats = Stats::from_samples(&samples);
+        assert_eq!((stats.min, stats.median, stats.max), (1, 2, 4));
+        assert_eq!(stats.iterations, 4);
+        assert_eq!(stats.stddev, 1);
+
+        let stats = Stats::from_samples(&[3, 1, 2].map(Duration::from_nanos));
+        assert_eq!((stats.median, stats.mean), (2, 2));
+    }
+
+    #[test]
+    fn test_bench() {
+        let res = bench::<Example>("1,2,3", 3);
+        let phases = res.iter().map(|(phase, _)| *phase).collect::<Vec<_>>();
+        assert_eq!(phases, [Phase::Parse, Phase::Part1, Phase::Part2]);
+        assert_eq!(res[0].1.as_ref().unwrap().iterations, 3);
+        assert!(res[1].1.is_ok());
+        assert_eq!(res[2].1, Err(Outcome::Unimplemented));
+
+        let res = bench::<Example>("1,x", 3);
+        assert!(res
+            .iter()
+            .all(|(_, stats)| matches!(stats, Err(Outcome::InvalidInput(_)))));
+    }
+
+    #[test]
+    fn test_compare() {
+        let record = |day, phase, median| Record {
+            day,
+            phase,
+            stats: stats(median),
+        };
+        let baseline = Report {
+            records: vec![
+                record(1, Phase::Parse, 100),
+                record(1, Phase::Part1, 100),
+                record(2, Phase::Part1, 100),
+            ],
+        };
+        let current = Report {
+            records: vec![
+                record(1, Phase::Parse, 105),
+                record(1, Phase::Part1, 150),
+                record(2, Phase::Part1, 50),
+                record(3, Phase::Part1, 1000),
+            ],
+        };
+
+        let regressions = compare(&baseline, &current, 10.0);
+        assert_eq!(
+            regressions,
+            vec![Regression {
+                day: 1,
+                phase: Phase::Part1,
+                baseline: 100,
+                current: 150,
+            }]
+        );
+        assert_eq!(
+            regressions[0].to_string(),
+            "day 01 part 1: 100.00ns -> 150.00ns (+50.0%)"
+        );
+    }
+
+    #[test]
+    fn test_report_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bench.json");
+        let report = Report {
+            records: vec![Record {
+                day: 5,
+                phase: Phase::Part2,
+                stats: stats(42),
+            }],
+        };
+        report.save(&path).unwrap();
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains("\"phase\": \"part2\""));
+        assert_eq!(Report::load(&path).unwrap(), report);
+    }
+}
@@ -0,0 +1,180 @@
+use std::{env, fmt::Display, fs, path::Path};
+
+/// Overrides the site, used to point the runner at a local server.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+/// Session cookie value, otherwise read from a `.session` file.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const YEAR: u16 = 2023;
+
+const USER_AGENT: &str = "aoc-2023-runner (ureq)";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// The little HTTP the runner needs, so tests can swap the site for a local server.
+/// Only fails when no response was received, any status is a [`Response`].
+pub trait HttpClient {
+    fn get(&self, url: &str, session: &str) -> Result<Response, String>;
+    /// Sends `form` url-encoded.
+    fn post_form(
+        &self,
+        url: &str,
+        session: &str,
+        form: &[(&str, &str)],
+    ) -> Result<Response, String>;
+}
+
+pub struct UreqClient {
+    agent: ureq::Agent,
+}
+
+impl Default for UreqClient {
+    fn default() -> Self {
+        let config = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .user_agent(USER_AGENT)
+            .build();
+        Self {
+            agent: ureq::Agent::new_with_config(config),
+        }
+    }
+}
+
+fn read(res: Result<ureq::http::Response<ureq::Body>, ureq::Error>) -> Result<Response, String> {
+    let mut res = res.map_err(|err| err.to_string())?;
+    let status = res.status().as_u16();
+    let body = res
+        .body_mut()
+        .read_to_string()
+        .map_err(|err| err.to_string())?;
+    Ok(Response { status, body })
+}
+
+impl HttpClient for UreqClient {
+    fn get(&self, url: &str, session: &str) -> Result<Response, String> {
+        let cookie = format!("session={}", session);
+        read(self.agent.get(url).header("Cookie", &cookie).call())
+    }
+
+    fn post_form(
+        &self,
+        url: &str,
+        session: &str,
+        form: &[(&str, &str)],
+    ) -> Result<Response, String> {
+        let cookie = format!("session={}", session);
+        read(
+            self.agent
+                .post(url)
+                .header("Cookie", &cookie)
+                .send_form(form.iter().copied()),
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SiteError {
+    NoSession,
+    Transport(String),
+    /// The session cookie was rejected or has expired.
+    Unauthorized,
+    /// Most likely the puzzle isn't unlocked yet.
+    NotFound(String),
+    Status {
+        status: u16,
+        body: String,
+    },
+}
+
+impl Display for SiteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoSession => write!(
+                f,
+                "no session token, set ${} or put it in a .session file",
+                SESSION_VAR
+            ),
+            Self::Transport(err) => write!(f, "request failed: {}", err),
+            Self::Unauthorized => write!(f, "the session token was rejected, log in again"),
+            Self::NotFound(url) => write!(f, "{} not found, is the puzzle unlocked?", url),
+            Self::Status { status, body } => {
+                write!(f, "unexpected status {}: {}", status, body.trim())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SiteError {}
+
+/// `$AOC_SESSION`, else the contents of `.session` in `dir` or any of its parents.
+pub fn session_token(dir: &Path) -> Option<String> {
+    if let Ok(token) = env::var(SESSION_VAR) {
+        return Some(token.trim().to_string()).filter(|t| !t.is_empty());
+    }
+    dir.ancestors()
+        .find_map(|dir| fs::read_to_string(dir.join(".session")).ok())
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
+/// The puzzle site for one session.
+pub struct Site<C> {
+    client: C,
+    base_url: String,
+    session: String,
+}
+
+impl<C: HttpClient> Site<C> {
+    pub fn new(client: C, base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        Self {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+        }
+    }
+
+    /// Uses `$AOC_BASE_URL` and the session from [`session_token`].
+    pub fn from_env(client: C, dir: &Path) -> Result<Self, SiteError> {
+        let session = session_token(dir).ok_or(SiteError::NoSession)?;
+        let base_url = env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        Ok(Self::new(client, base_url, session))
+    }
+
+    pub fn day_url(&self, day: u8) -> String {
+        format!("{}/{}/day/{}", self.base_url, YEAR, day)
+    }
+
+    /// The body of a successful response, errors for every other status.
+    fn check(url: String, res: Result<Response, String>) -> Result<String, SiteError> {
+        let res = res.map_err(SiteError::Transport)?;
+        match res.status {
+            200 => Ok(res.body),
+            // the site answers 400 rather than 401 for a bad cookie
+            400 | 401 | 403 => Err(SiteError::Unauthorized),
+            404 => Err(SiteError::NotFound(url)),
+            status => Err(SiteError::Status {
+                status,
+                body: res.body,
+            }),
+        }
+    }
+
+    pub fn input(&self, day: u8) -> Result<String, SiteError> {
+        let url = format!("{}/input", self.day_url(day));
+        let res = self.client.get(&url, &self.session);
+        Self::check(url, res)
+    }
+
+    /// The HTML page the site replies with, see [`crate::submit::Reply`].
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<String, SiteError> {
+        let url = format!("{}/answer", self.day_url(day));
+        let level = part.to_string();
+        let form = [("level", level.as_str()), ("answer", answer)];
+        let res = self.client.post_form(&url, &self.session, &form);
+        Self::check(url, res)
+    }
+}
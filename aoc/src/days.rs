@@ -0,0 +1,51 @@
+use aoc_core::Solution;
+
+use crate::{
+    bench::{self, Bencher},
+    runner::{self, Outcome},
+};
+
+/// Parses the input once and solves the given parts.
+pub type Runner = fn(&str, &[u8]) -> Vec<Outcome>;
+
+pub struct Day {
+    pub day: u8,
+    pub run: Runner,
+    pub bench: Bencher,
+}
+
+macro_rules! day {
+    ($solution:ty) => {
+        Day {
+            day: <$solution>::DAY,
+            run: runner::run::<$solution>,
+            bench: bench::bench::<$solution>,
+        }
+    };
+}
+
+pub const DAYS: &[Day] = &[
+    day!(day_01::Day01),
+    day!(day_02::Day02),
+    day!(day_03::Day03),
+    day!(day_04::Day04),
+    day!(day_05::Day05),
+    day!(day_06::Day06),
+    day!(day_07::Day07),
+    day!(day_08::Day08),
+    day!(day_09::Day09),
+    day!(day_10::Day10),
+    day!(day_11::Day11),
+    day!(day_12::Day12),
+    day!(day_13::Day13),
+    day!(day_14::Day14),
+    day!(day_15::Day15),
+    day!(day_16::Day16),
+    day!(day_17::Day17),
+    day!(day_18::Day18),
+    day!(day_19::Day19),
+];
+
+pub fn get(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
@@ -0,0 +1,146 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::client::{HttpClient, Site, SiteError};
+
+#[derive(Debug)]
+pub enum FetchError {
+    Site(SiteError),
+    Write { path: PathBuf, error: io::Error },
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Site(err) => write!(f, "{}", err),
+            Self::Write { path, error } => {
+                write!(f, "could not write {}: {}", path.display(), error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl From<SiteError> for FetchError {
+    fn from(err: SiteError) -> Self {
+        Self::Site(err)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fetched {
+    pub path: PathBuf,
+    /// Already on disk, nothing was downloaded.
+    pub cached: bool,
+}
+
+/// Downloads inputs into `dir` as `day-NN.txt`, where the input loader looks for them.
+/// Each day is only ever requested once.
+pub struct Fetcher<C> {
+    site: Site<C>,
+    dir: PathBuf,
+}
+
+impl<C: HttpClient> Fetcher<C> {
+    pub fn new(site: Site<C>, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            site,
+            dir: dir.into(),
+        }
+    }
+
+    pub fn fetch(&self, day: u8) -> Result<Fetched, FetchError> {
+        let path = self.dir.join(format!("day-{:02}.txt", day));
+        if path.is_file() {
+            return Ok(Fetched { path, cached: true });
+        }
+
+        let text = self.site.input(day)?;
+        write(&path, &text).map_err(|error| FetchError::Write {
+            path: path.clone(),
+            error,
+        })?;
+        Ok(Fetched {
+            path,
+            cached: false,
+        })
+    }
+}
+
+// write next to the target first, an interrupted download must not look cached
+fn write(path: &Path, text: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let partial = path.with_extension("txt.partial");
+    fs::write(&partial, text)?;
+    fs::rename(&partial, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{client::UreqClient, mock::MockServer};
+
+    use super::*;
+
+    #[test]
+    fn test_fetch_once() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/2023/day/6/input" => (200, "Time: 7\nDistance: 9\n".to_string()),
+            _ => (404, "404 Not Found".to_string()),
+        });
+        let dir = tempfile::tempdir().unwrap();
+        let site = Site::new(UreqClient::default(), &server.url, "cookie");
+        let fetcher = Fetcher::new(site, dir.path().join("inputs"));
+
+        let fetched = fetcher.fetch(6).unwrap();
+        assert_eq!(
+            fetched,
+            Fetched {
+                path: dir.path().join("inputs/day-06.txt"),
+                cached: false,
+            }
+        );
+        assert_eq!(
+            fs::read_to_string(&fetched.path).unwrap(),
+            "Time: 7\nDistance: 9\n"
+        );
+
+        assert!(fetcher.fetch(6).unwrap().cached);
+        let received = server.received();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].method, "GET");
+        assert_eq!(received[0].cookie.as_deref(), Some("session=cookie"));
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/2023/day/1/input" => (
+                400,
+                "Puzzle inputs differ by user.  Please log in.".to_string(),
+            ),
+            _ => (404, "404 Not Found".to_string()),
+        });
+        let dir = tempfile::tempdir().unwrap();
+        let site = Site::new(UreqClient::default(), format!("{}/", server.url), "expired");
+        let fetcher = Fetcher::new(site, dir.path());
+
+        assert!(matches!(
+            fetcher.fetch(1),
+            Err(FetchError::Site(SiteError::Unauthorized))
+        ));
+        match fetcher.fetch(25) {
+            Err(FetchError::Site(SiteError::NotFound(url))) => {
+                assert_eq!(url, format!("{}/2023/day/25/input", server.url))
+            }
+            other => panic!("expected not found, got {:?}", other),
+        }
+        // nothing is cached after a failed download
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+}
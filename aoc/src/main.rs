@@ -0,0 +1,492 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use answers::{Ledger, Recorded, Verdict};
+use aoc_core::{
+    input::{Input, InputLoader},
+    Answer,
+};
+use bench::{Record, Report};
+use clap::{Parser, Subcommand};
+use client::{Site, UreqClient};
+use fetch::Fetcher;
+use submit::{Feedback, History};
+
+use runner::Outcome;
+
+mod answers;
+mod bench;
+mod client;
+mod days;
+mod fetch;
+#[cfg(test)]
+mod mock;
+mod runner;
+mod scaffold;
+mod submit;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2023 runner")]
+struct Cli {
+    /// Show what the solutions are doing on stderr, repeat for more detail
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a single part or every day
+    Run {
+        #[arg(required_unless_present = "all", value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        #[arg(required_unless_present = "all", value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Puzzle input, otherwise stdin, `$AOC_INPUT_DIR` or `inputs/day-NN.txt`
+        #[arg(long, conflicts_with = "all")]
+        input: Option<PathBuf>,
+        /// Run both parts of every day
+        #[arg(long, conflicts_with_all = ["day", "part"])]
+        all: bool,
+        /// Add solved answers to the ledger, never replacing a recorded one
+        #[arg(long)]
+        record: bool,
+        /// Answers ledger
+        #[arg(long, default_value = answers::DEFAULT_PATH)]
+        answers: PathBuf,
+    },
+    /// Create a crate for a new day from the template and register it with the runner
+    New {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Download a day's input into the inputs folder, unless it is already there
+    Fetch {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Submit an answer, computed from the puzzle input unless given
+    Submit {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        answer: Option<String>,
+        /// Puzzle input, otherwise stdin, `$AOC_INPUT_DIR` or `inputs/day-NN.txt`
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Every submission made so far
+        #[arg(long, default_value = submit::DEFAULT_PATH)]
+        history: PathBuf,
+        /// Answers ledger, a correct answer is added to it
+        #[arg(long, default_value = answers::DEFAULT_PATH)]
+        answers: PathBuf,
+    },
+    /// Check every implemented part against the recorded answers
+    Verify {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        /// Answers ledger
+        #[arg(long, default_value = answers::DEFAULT_PATH)]
+        answers: PathBuf,
+    },
+    /// Time parsing and both parts, every day unless one is given
+    Bench {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        /// Runs of each phase
+        #[arg(short = 'n', long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+        iterations: u64,
+        /// Write the results as JSON
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// Results of an earlier `--output` to check for regressions
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+        /// Allowed slowdown of the median against the baseline, in percent
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+}
+
+fn run_parts(day: &days::Day, parts: &[u8], input: &InputLoader) -> (Option<Input>, Vec<Outcome>) {
+    match input.load() {
+        Ok(input) => {
+            let outcomes = (day.run)(&input.text, parts);
+            (Some(input), outcomes)
+        }
+        Err(err) => (
+            None,
+            vec![Outcome::MissingInput(err.to_string()); parts.len()],
+        ),
+    }
+}
+
+/// The given day or every registered one.
+fn select(day: Option<u8>) -> Result<Vec<&'static days::Day>, ExitCode> {
+    match day {
+        Some(day) => match days::get(day) {
+            Some(solution) => Ok(vec![solution]),
+            None => {
+                eprintln!("day {:02}: no solution registered", day);
+                Err(ExitCode::from(Outcome::Unimplemented.exit_code()))
+            }
+        },
+        None => Ok(days::DAYS.iter().collect()),
+    }
+}
+
+fn load_ledger(path: &Path) -> Result<Ledger, ExitCode> {
+    Ledger::load(path).map_err(|err| {
+        eprintln!("could not read {}: {}", path.display(), err);
+        ExitCode::FAILURE
+    })
+}
+
+/// Adds the solved parts to the ledger at `path`.
+fn record(path: &Path, day: u8, input: &Input, outcomes: &[(u8, Outcome)]) -> Result<(), ExitCode> {
+    let mut ledger = load_ledger(path)?;
+    for (part, outcome) in outcomes {
+        let Outcome::Solved { answer, .. } = outcome else {
+            continue;
+        };
+        let source = input.source.to_string();
+        if let Recorded::Conflict(recorded) =
+            ledger.record(day, &input.text, &source, *part, answer)
+        {
+            eprintln!(
+                "day {:02} part {}: not recording {}, {} is already recorded",
+                day, part, answer, recorded
+            );
+        }
+    }
+    ledger.save(path).map_err(|err| {
+        eprintln!("could not write {}: {}", path.display(), err);
+        ExitCode::FAILURE
+    })
+}
+
+fn verify(day: Option<u8>, answers: &Path) -> ExitCode {
+    let (selected, ledger) = match (select(day), load_ledger(answers)) {
+        (Ok(selected), Ok(ledger)) => (selected, ledger),
+        (Err(code), _) | (_, Err(code)) => return code,
+    };
+
+    let mut failed = false;
+    println!(
+        "{:<4} {:<5} {:<13} {:<20} expected",
+        "day", "part", "status", "answer"
+    );
+    for day in selected {
+        let (input, outcomes) = run_parts(day, &[1, 2], &InputLoader::new(day.day).stdin(false));
+        for (part, outcome) in (1..=2).zip(outcomes) {
+            let expected = input
+                .as_ref()
+                .and_then(|input| ledger.get(day.day, &input.text, part));
+            let verdict = Verdict::new(&outcome, expected);
+            failed |= verdict.is_failure();
+
+            let answer = match (&outcome, &verdict) {
+                (Outcome::Solved { answer, .. }, _) => answer.to_string(),
+                (_, Verdict::Fail(msg)) => msg.clone(),
+                _ => String::new(),
+            };
+            let expected = match &verdict {
+                Verdict::Mismatch { expected } => expected.as_str(),
+                _ => "",
+            };
+            let row = format!(
+                "{:<4} {:<5} {:<13} {:<20} {}",
+                format!("{:02}", day.day),
+                part,
+                verdict,
+                answer,
+                expected
+            );
+            println!("{}", row.trim_end());
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn new_day(day: u8) -> ExitCode {
+    let cwd = env::current_dir().unwrap_or_default();
+    let created = scaffold::workspace_root(&cwd).and_then(|root| scaffold::create(&root, day));
+    match created {
+        Ok(created) => {
+            for path in created {
+                eprintln!("created {}", path.display());
+            }
+            eprintln!("day {:02}: registered with the runner", day);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("day {:02}: {}", day, err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn fetch(day: u8) -> ExitCode {
+    let cwd = env::current_dir().unwrap_or_default();
+    let site = match Site::from_env(UreqClient::default(), &cwd) {
+        Ok(site) => site,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match Fetcher::new(site, InputLoader::new(day).cache_dir()).fetch(day) {
+        Ok(fetched) if fetched.cached => {
+            eprintln!("day {:02}: already at {}", day, fetched.path.display());
+            ExitCode::SUCCESS
+        }
+        Ok(fetched) => {
+            eprintln!("day {:02}: saved to {}", day, fetched.path.display());
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("day {:02}: {}", day, err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn submit_answer(
+    day: u8,
+    part: u8,
+    answer: Option<String>,
+    input: Option<PathBuf>,
+    history_path: &Path,
+    answers: &Path,
+) -> ExitCode {
+    let loader = InputLoader::new(day).path(input);
+    let (input, outcome) = match answer {
+        Some(answer) => {
+            let outcome = Outcome::Solved {
+                answer: Answer::from(answer),
+                elapsed: Duration::ZERO,
+            };
+            (loader.load().ok(), outcome)
+        }
+        None => {
+            let Some(solution) = days::get(day) else {
+                eprintln!("day {:02}: no solution registered", day);
+                return ExitCode::from(Outcome::Unimplemented.exit_code());
+            };
+            let (input, mut outcomes) = run_parts(solution, &[part], &loader);
+            (input, outcomes.remove(0))
+        }
+    };
+    let Outcome::Solved { answer, .. } = &outcome else {
+        eprintln!("day {:02} part {}: {}", day, part, outcome);
+        return ExitCode::from(outcome.exit_code());
+    };
+    let answer = answer.to_string();
+
+    let cwd = env::current_dir().unwrap_or_default();
+    let site = match Site::from_env(UreqClient::default(), &cwd) {
+        Ok(site) => site,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut history = match History::load(history_path) {
+        Ok(history) => history,
+        Err(err) => {
+            eprintln!("could not read {}: {}", history_path.display(), err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let reply = match submit::submit(&site, &mut history, day, part, &answer, now) {
+        Ok(reply) => reply,
+        Err(err) => {
+            eprintln!("day {:02} part {}: {}", day, part, err);
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(err) = history.save(history_path) {
+        eprintln!("could not write {}: {}", history_path.display(), err);
+    }
+
+    println!(
+        "day {:02} part {}: {} is {}",
+        day, part, answer, reply.feedback
+    );
+    if let Some(wait) = reply.wait {
+        eprintln!("next submission possible in {}s", wait.as_secs());
+    }
+    if reply.feedback != Feedback::Correct {
+        return ExitCode::FAILURE;
+    }
+    if let Some(input) = input {
+        if let Err(code) = record(answers, day, &input, &[(part, outcome)]) {
+            return code;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn bench(
+    day: Option<u8>,
+    iterations: usize,
+    output: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+) -> ExitCode {
+    let selected = match select(day) {
+        Ok(selected) => selected,
+        Err(code) => return code,
+    };
+
+    let mut report = Report::default();
+    for day in selected {
+        let input = match InputLoader::new(day.day).stdin(false).load() {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("day {:02}: skipped, {}", day.day, err);
+                continue;
+            }
+        };
+        for (phase, res) in (day.bench)(&input.text, iterations) {
+            match res {
+                Ok(stats) => {
+                    println!("day {:02} {:<6}  {}", day.day, phase, stats);
+                    report.records.push(Record {
+                        day: day.day,
+                        phase,
+                        stats,
+                    });
+                }
+                Err(outcome) => println!("day {:02} {:<6}  {}", day.day, phase, outcome),
+            }
+        }
+    }
+
+    if let Some(path) = output {
+        if let Err(err) = report.save(&path) {
+            eprintln!("could not write {}: {}", path.display(), err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if let Some(path) = baseline {
+        let baseline = match Report::load(&path) {
+            Ok(baseline) => baseline,
+            Err(err) => {
+                eprintln!("could not read baseline {}: {}", path.display(), err);
+                return ExitCode::FAILURE;
+            }
+        };
+        let regressions = bench::compare(&baseline, &report, threshold);
+        if !regressions.is_empty() {
+            eprintln!("regressions against {}:", path.display());
+            for regression in regressions {
+                eprintln!("  {}", regression);
+            }
+            return ExitCode::FAILURE;
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    aoc_core::trace::init(cli.verbose);
+
+    match cli.command {
+        Command::Run {
+            all: true,
+            record: should_record,
+            answers,
+            ..
+        } => {
+            let mut code = 0;
+            for day in days::DAYS {
+                // stdin can only be consumed once, so every day is looked up on disk
+                let loader = InputLoader::new(day.day).stdin(false);
+                let (input, outcomes) = run_parts(day, &[1, 2], &loader);
+                let outcomes = (1..=2).zip(outcomes).collect::<Vec<_>>();
+                for (part, outcome) in &outcomes {
+                    println!("day {:02} part {}: {}", day.day, part, outcome);
+                    code = code.max(outcome.exit_code());
+                }
+                if let (true, Some(input)) = (should_record, input) {
+                    if let Err(code) = record(&answers, day.day, &input, &outcomes) {
+                        return code;
+                    }
+                }
+            }
+            ExitCode::from(code)
+        }
+        Command::Run {
+            day,
+            part,
+            input,
+            record: should_record,
+            answers,
+            ..
+        } => {
+            let (day, part) = (day.unwrap(), part.unwrap());
+            let Some(solution) = days::get(day) else {
+                eprintln!("day {:02}: no solution registered", day);
+                return ExitCode::from(Outcome::Unimplemented.exit_code());
+            };
+            let loader = InputLoader::new(day).path(input);
+            let (input, mut outcomes) = run_parts(solution, &[part], &loader);
+            let outcome = outcomes.remove(0);
+
+            if let (true, Some(input)) = (should_record, &input) {
+                if let Err(code) = record(&answers, day, input, &[(part, outcome.clone())]) {
+                    return code;
+                }
+            }
+
+            match outcome {
+                Outcome::Solved { answer, .. } => {
+                    println!("{}", answer);
+                    ExitCode::SUCCESS
+                }
+                outcome => {
+                    eprintln!("day {:02} part {}: {}", day, part, outcome);
+                    ExitCode::from(outcome.exit_code())
+                }
+            }
+        }
+        Command::Bench {
+            day,
+            iterations,
+            output,
+            baseline,
+            threshold,
+        } => bench(day, iterations as usize, output, baseline, threshold),
+        Command::New { day } => new_day(day),
+        Command::Fetch { day } => fetch(day),
+        Command::Submit {
+            day,
+            part,
+            answer,
+            input,
+            history,
+            answers,
+        } => submit_answer(day, part, answer, input, &history, &answers),
+        Command::Verify { day, answers } => verify(day, &answers),
+    }
+}
@@ -0,0 +1,84 @@
+//! A local stand-in for the puzzle site, so the real HTTP client can be tested end to end.
+
+use std::{
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+};
+
+use tiny_http::{Header, Response, Server};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Received {
+    pub method: String,
+    pub path: String,
+    pub cookie: Option<String>,
+    pub body: String,
+}
+
+type Handler = dyn Fn(&Received) -> (u16, String) + Send + Sync;
+
+pub struct MockServer {
+    pub url: String,
+    received: Arc<Mutex<Vec<Received>>>,
+    server: Arc<Server>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Serves every request with `handler` until dropped.
+    pub fn start(handler: impl Fn(&Received) -> (u16, String) + Send + Sync + 'static) -> Self {
+        let server = Arc::new(Server::http("127.0.0.1:0").unwrap());
+        let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let received = Arc::new(Mutex::new(vec![]));
+        let handler: Box<Handler> = Box::new(handler);
+
+        let thread = {
+            let server = server.clone();
+            let received = received.clone();
+            thread::spawn(move || {
+                for mut request in server.incoming_requests() {
+                    let mut body = String::new();
+                    request.as_reader().read_to_string(&mut body).unwrap();
+                    let cookie = request
+                        .headers()
+                        .iter()
+                        .find(|h| h.field.equiv("Cookie"))
+                        .map(|h| h.value.to_string());
+                    let got = Received {
+                        method: request.method().to_string(),
+                        path: request.url().to_string(),
+                        cookie,
+                        body,
+                    };
+                    let (status, text) = handler(&got);
+                    received.lock().unwrap().push(got);
+                    let content_type = Header::from_bytes("Content-Type", "text/plain").unwrap();
+                    let response = Response::from_string(text)
+                        .with_status_code(status)
+                        .with_header(content_type);
+                    let _ = request.respond(response);
+                }
+            })
+        };
+
+        Self {
+            url,
+            received,
+            server,
+            thread: Some(thread),
+        }
+    }
+
+    pub fn received(&self) -> Vec<Received> {
+        self.received.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+    time::{Duration, Instant},
+};
+
+use aoc_core::{Answer, ParseError, Solution};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Solved { answer: Answer, elapsed: Duration },
+    Unimplemented,
+    Panicked(String),
+    MissingInput(String),
+    InvalidInput(ParseError),
+}
+
+impl Outcome {
+    /// Process exit code, `run --all` exits with the highest one.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::Solved { .. } => 0,
+            Self::Unimplemented => 3,
+            Self::MissingInput(_) => 4,
+            Self::InvalidInput(_) => 5,
+            Self::Panicked(_) => 6,
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Solved { answer, elapsed } => write!(f, "{} ({:.2?})", answer, elapsed),
+            Self::Unimplemented => write!(f, "not implemented"),
+            Self::Panicked(msg) => write!(f, "panicked: {}", msg),
+            Self::MissingInput(msg) => write!(f, "missing input: {}", msg),
+            Self::InvalidInput(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+pub fn catch<T>(f: impl FnOnce() -> T) -> Result<T, Outcome> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        let msg = panic_message(payload.as_ref());
+        // `todo!()` and `unimplemented!()` mark parts we haven't solved yet
+        if msg.starts_with("not yet implemented") || msg.starts_with("not implemented") {
+            Outcome::Unimplemented
+        } else {
+            Outcome::Panicked(msg)
+        }
+    })
+}
+
+thread_local! {
+    // set while the thread's panics are reported as an `Outcome` instead
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` without the default panic hook printing its panics, every `todo!()`
+/// would end up on stderr while we report it ourselves. The hook is replaced
+/// once for the whole process and only stays silent on threads inside `quietly`,
+/// so other threads still get their panics printed.
+pub fn quietly<T>(f: impl FnOnce() -> T) -> T {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.get() {
+                default(info);
+            }
+        }));
+    });
+    let quiet = QUIET.replace(true);
+    let res = f();
+    QUIET.set(quiet);
+    res
+}
+
+/// Parses the input, a panic or [`ParseError`] becomes the [`Outcome`] of every part.
+pub fn parse<S: Solution>(input: &str) -> Result<S::Input<'_>, Outcome> {
+    catch(|| S::parse(input)).and_then(|res| res.map_err(Outcome::InvalidInput))
+}
+
+pub fn solve<S: Solution>(parsed: &S::Input<'_>, part: u8) -> Result<Answer, Outcome> {
+    catch(|| match part {
+        1 => S::part1(parsed),
+        2 => S::part2(parsed),
+        _ => unreachable!("part is validated by the cli"),
+    })
+}
+
+/// Parses the input once and solves the requested parts, turning panics into an
+/// [`Outcome`] instead of aborting the runner.
+pub fn run<S: Solution>(input: &str, parts: &[u8]) -> Vec<Outcome> {
+    quietly(|| match parse::<S>(input) {
+        Ok(parsed) => parts
+            .iter()
+            .map(|&part| {
+                let _span = tracing::info_span!("solve", day = S::DAY, part).entered();
+                let start = Instant::now();
+                let res = solve::<S>(&parsed, part);
+                let elapsed = start.elapsed();
+                match res {
+                    Ok(answer) => Outcome::Solved { answer, elapsed },
+                    Err(outcome) => outcome,
+                }
+            })
+            .collect(),
+        Err(outcome) => vec![outcome; parts.len()],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Example;
+
+    impl Solution for Example {
+        const DAY: u8 = 0;
+
+        type Input<'a> = Vec<&'a str>;
+
+        fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+            if input.is_empty() {
+                panic!("huh");
+            }
+            if let Some(at) = input.find('!') {
+                return Err(ParseError::new(Self::DAY, input, &input[at..], "a letter"));
+            }
+            Ok(input.lines().collect())
+        }
+
+        fn part1(input: &Self::Input<'_>) -> Answer {
+            input.len().into()
+        }
+
+        fn part2(_input: &Self::Input<'_>) -> Answer {
+            todo!();
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let res = run::<Example>("a\nb\nc", &[1, 2]);
+        assert!(matches!(&res[0], Outcome::Solved { answer, .. } if answer == &"3"));
+        assert_eq!(res[1], Outcome::Unimplemented);
+        assert_eq!(res[1].exit_code(), 3);
+    }
+
+    #[test]
+    fn test_parse_panicked() {
+        let res = run::<Example>("", &[1, 2]);
+        assert_eq!(res, vec![Outcome::Panicked("huh".to_string()); 2]);
+        assert_eq!(res[0].exit_code(), 6);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let res = run::<Example>("a\nb!", &[1]);
+        let Outcome::InvalidInput(err) = &res[0] else {
+            panic!("expected invalid input, got {:?}", res[0]);
+        };
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(res[0].exit_code(), 5);
+    }
+
+    #[test]
+    fn test_quietly() {
+        assert!(!QUIET.get());
+        quietly(|| {
+            quietly(|| assert!(QUIET.get()));
+            assert!(QUIET.get());
+            // other threads keep the default hook
+            std::thread::spawn(|| assert!(!QUIET.get())).join().unwrap();
+        });
+        assert!(!QUIET.get());
+    }
+}
@@ -0,0 +1,251 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The crate `new` creates, relative to its directory. The placeholders are
+/// `{{crate}}`, `{{crate_name}}`, `{{struct}}` and `{{day}}`, see [`render`].
+const TEMPLATE: &[(&str, &str)] = &[
+    ("Cargo.toml", include_str!("../../template/Cargo.toml")),
+    ("build.rs", include_str!("../../template/build.rs")),
+    ("src/lib.rs", include_str!("../../template/src/lib.rs")),
+    (
+        "src/bin/part1.rs",
+        include_str!("../../template/src/bin/part1.rs"),
+    ),
+    (
+        "src/bin/part2.rs",
+        include_str!("../../template/src/bin/part2.rs"),
+    ),
+    (
+        "benches/solve.rs",
+        include_str!("../../template/benches/solve.rs"),
+    ),
+    (
+        "examples/example.in",
+        include_str!("../../template/examples/example.in"),
+    ),
+];
+
+/// Where the runner lists its days, relative to the workspace root.
+const MANIFEST: &str = "aoc/Cargo.toml";
+const DAYS: &str = "aoc/src/days.rs";
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    NoWorkspace,
+    Exists(PathBuf),
+    Registered(u8),
+    /// The file has no day entries to insert the new one next to.
+    NoAnchor(PathBuf),
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoWorkspace => write!(f, "not inside the workspace, no {} found", DAYS),
+            Self::Exists(path) => write!(f, "{} already exists", path.display()),
+            Self::Registered(day) => {
+                write!(f, "day {:02} is already registered with the runner", day)
+            }
+            Self::NoAnchor(path) => {
+                write!(f, "no days found in {} to add this one to", path.display())
+            }
+            Self::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+fn io_error(path: &Path) -> impl FnOnce(io::Error) -> ScaffoldError + '_ {
+    move |error| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        error,
+    }
+}
+
+/// The nearest ancestor of `dir` holding the runner.
+pub fn workspace_root(dir: &Path) -> Result<PathBuf, ScaffoldError> {
+    dir.ancestors()
+        .find(|dir| dir.join(DAYS).is_file() && dir.join(MANIFEST).is_file())
+        .map(Path::to_path_buf)
+        .ok_or(ScaffoldError::NoWorkspace)
+}
+
+pub fn render(template: &str, day: u8) -> String {
+    template
+        .replace("{{crate}}", &format!("day-{:02}", day))
+        .replace("{{crate_name}}", &format!("day_{:02}", day))
+        .replace("{{struct}}", &format!("Day{:02}", day))
+        .replace("{{day}}", &day.to_string())
+}
+
+/// Inserts `line` among the lines `day_of` recognises, keeping them ordered by day.
+/// `None` when there are none to go by.
+fn insert_day(
+    text: &str,
+    day: u8,
+    line: &str,
+    day_of: impl Fn(&str) -> Option<u8>,
+) -> Option<String> {
+    let mut lines = text.lines().collect::<Vec<_>>();
+    let days = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, day_of(line.trim())?)))
+        .collect::<Vec<_>>();
+    let (last, _) = *days.last()?;
+    let at = days
+        .iter()
+        .find(|(_, other)| *other > day)
+        .map_or(last + 1, |(i, _)| *i);
+    lines.insert(at, line);
+
+    let mut text = lines.join("\n");
+    text.push('\n');
+    Some(text)
+}
+
+fn manifest_day(line: &str) -> Option<u8> {
+    let (name, _) = line.split_once(" = ")?;
+    name.strip_prefix("day-")?.parse().ok()
+}
+
+fn registered_day(line: &str) -> Option<u8> {
+    line.strip_prefix("day!(day_")?.get(..2)?.parse().ok()
+}
+
+/// Adds the new crate as a dependency of the runner.
+pub fn register_dependency(manifest: &str, day: u8) -> Option<String> {
+    let line = format!("day-{:02} = {{ path = \"../day-{:02}\" }}", day, day);
+    insert_day(manifest, day, &line, manifest_day)
+}
+
+/// Adds the new solution to [`crate::days::DAYS`].
+pub fn register_day(days: &str, day: u8) -> Option<String> {
+    let line = format!("    day!(day_{:02}::Day{:02}),", day, day);
+    insert_day(days, day, &line, registered_day)
+}
+
+/// Creates `day-NN` in the workspace at `root` and registers it with the runner.
+/// Nothing is written if the day exists already. Returns the created files.
+pub fn create(root: &Path, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let dir = root.join(format!("day-{:02}", day));
+    if dir.exists() {
+        return Err(ScaffoldError::Exists(dir));
+    }
+
+    let manifest_path = root.join(MANIFEST);
+    let days_path = root.join(DAYS);
+    let manifest = fs::read_to_string(&manifest_path).map_err(io_error(&manifest_path))?;
+    let days = fs::read_to_string(&days_path).map_err(io_error(&days_path))?;
+    if days
+        .lines()
+        .any(|line| registered_day(line.trim()) == Some(day))
+    {
+        return Err(ScaffoldError::Registered(day));
+    }
+    // work out both edits before touching anything
+    let manifest = register_dependency(&manifest, day)
+        .ok_or(ScaffoldError::NoAnchor(manifest_path.clone()))?;
+    let days = register_day(&days, day).ok_or(ScaffoldError::NoAnchor(days_path.clone()))?;
+
+    let mut created = vec![];
+    for (name, template) in TEMPLATE {
+        let path = dir.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_error(parent))?;
+        }
+        fs::write(&path, render(template, day)).map_err(io_error(&path))?;
+        created.push(path);
+    }
+    fs::write(&manifest_path, manifest).map_err(io_error(&manifest_path))?;
+    fs::write(&days_path, days).map_err(io_error(&days_path))?;
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("aoc/src")).unwrap();
+        fs::write(dir.path().join(MANIFEST), include_str!("../Cargo.toml")).unwrap();
+        fs::write(dir.path().join(DAYS), include_str!("days.rs")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_render() {
+        for (name, template) in TEMPLATE {
+            let text = render(template, 7);
+            assert!(!text.contains("{{"), "{} has placeholders left", name);
+        }
+        let template = |name| TEMPLATE.iter().find(|(n, _)| *n == name).unwrap().1;
+        let lib = render(template("src/lib.rs"), 7);
+        assert!(lib.contains("pub struct Day07;"));
+        assert!(lib.contains("const DAY: u8 = 7;"));
+        assert!(render(template("src/bin/part1.rs"), 7).contains("use day_07::Day07;"));
+    }
+
+    #[test]
+    fn test_register() {
+        let manifest = "[dependencies]\naoc-core = { path = \"../aoc-core\" }\nday-01 = { path = \"../day-01\" }\nday-03 = { path = \"../day-03\" }\nserde = \"1\"\n";
+        assert_eq!(
+            register_dependency(manifest, 2).unwrap(),
+            "[dependencies]\naoc-core = { path = \"../aoc-core\" }\nday-01 = { path = \"../day-01\" }\nday-02 = { path = \"../day-02\" }\nday-03 = { path = \"../day-03\" }\nserde = \"1\"\n"
+        );
+        assert!(register_dependency(manifest, 4).unwrap().contains(
+            "day-03 = { path = \"../day-03\" }\nday-04 = { path = \"../day-04\" }\nserde"
+        ));
+        assert_eq!(register_dependency("[dependencies]\n", 1), None);
+
+        let days = "pub const DAYS: &[Day] = &[\n    day!(day_01::Day01),\n];\n";
+        assert_eq!(
+            register_day(days, 12).unwrap(),
+            "pub const DAYS: &[Day] = &[\n    day!(day_01::Day01),\n    day!(day_12::Day12),\n];\n"
+        );
+    }
+
+    #[test]
+    fn test_create() {
+        let root = workspace();
+        let root = root.path();
+        assert_eq!(workspace_root(&root.join("inputs/nested")).unwrap(), root);
+
+        let created = create(root, 25).unwrap();
+        assert_eq!(created.len(), TEMPLATE.len());
+        assert!(root.join("day-25/src/lib.rs").is_file());
+        assert!(root.join("day-25/benches/solve.rs").is_file());
+        let manifest = fs::read_to_string(root.join(MANIFEST)).unwrap();
+        assert!(manifest.contains("day-25 = { path = \"../day-25\" }\nserde"));
+        let days = fs::read_to_string(root.join(DAYS)).unwrap();
+        assert!(days.contains("    day!(day_25::Day25),\n];"));
+
+        // the day exists, nothing changes
+        fs::write(root.join("day-25/src/lib.rs"), "mine").unwrap();
+        assert!(matches!(create(root, 25), Err(ScaffoldError::Exists(_))));
+        assert_eq!(
+            fs::read_to_string(root.join("day-25/src/lib.rs")).unwrap(),
+            "mine"
+        );
+        assert_eq!(fs::read_to_string(root.join(DAYS)).unwrap(), days);
+    }
+
+    #[test]
+    fn test_create_registered() {
+        let root = workspace();
+        assert!(matches!(
+            create(root.path(), 1),
+            Err(ScaffoldError::Registered(1))
+        ));
+        assert!(!root.path().join("day-01").exists());
+    }
+}
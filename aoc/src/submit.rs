@@ -0,0 +1,382 @@
+use std::{fmt::Display, fs, io, path::Path, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::{HttpClient, Site, SiteError};
+
+pub const DEFAULT_PATH: &str = "submissions.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Feedback {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong without a hint, e.g. for text answers.
+    Wrong,
+    /// Submitted during the cooldown, the answer wasn't checked.
+    TooRecent,
+    /// The part is already solved, or part 1 isn't yet.
+    WrongLevel,
+}
+
+impl Display for Feedback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Self::Correct => "correct",
+            Self::TooHigh => "too high",
+            Self::TooLow => "too low",
+            Self::Wrong => "wrong",
+            Self::TooRecent => "submitted too recently",
+            Self::WrongLevel => "not the current level, already solved?",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reply {
+    pub feedback: Feedback,
+    /// How long the site wants us to wait before the next submission.
+    pub wait: Option<Duration>,
+}
+
+impl Reply {
+    /// Reads the `<article>` the site answers a submission with, `None` if it is
+    /// none of the known messages.
+    pub fn parse(html: &str) -> Option<Self> {
+        let text = html.to_lowercase();
+        let feedback = if text.contains("that's the right answer") {
+            Feedback::Correct
+        } else if text.contains("you gave an answer too recently") {
+            Feedback::TooRecent
+        } else if text.contains("that's not the right answer") {
+            if text.contains("your answer is too high") {
+                Feedback::TooHigh
+            } else if text.contains("your answer is too low") {
+                Feedback::TooLow
+            } else {
+                Feedback::Wrong
+            }
+        } else if text.contains("you don't seem to be solving the right level") {
+            Feedback::WrongLevel
+        } else {
+            return None;
+        };
+        Some(Self {
+            feedback,
+            wait: parse_wait(&text),
+        })
+    }
+}
+
+// "you have 1m 5s left to wait" or "please wait 5 minutes before trying again"
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some((before, _)) = text.split_once(" left to wait") {
+        let (_, left) = before.rsplit_once("you have ")?;
+        let secs = left.split_whitespace().try_fold(0, |acc, part| {
+            let (n, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let n = n.parse::<u64>().ok()?;
+            match unit {
+                "h" => Some(acc + n * 3600),
+                "m" => Some(acc + n * 60),
+                "s" => Some(acc + n),
+                _ => None,
+            }
+        })?;
+        return Some(Duration::from_secs(secs));
+    }
+
+    let (_, rest) = text.split_once("please wait ")?;
+    let (amount, _) = rest.split_once(" before trying again")?;
+    let (n, unit) = amount.split_once(' ')?;
+    let n = match n {
+        "one" => 1,
+        n => n.parse().ok()?,
+    };
+    match unit.trim_end_matches('s') {
+        "minute" => Some(Duration::from_secs(n * 60)),
+        "second" => Some(Duration::from_secs(n)),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub feedback: Feedback,
+    /// Unix time in seconds.
+    pub at: u64,
+}
+
+/// Why an answer was not sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    Solved(String),
+    AlreadyRejected(Feedback),
+    /// A numeric answer outside what earlier too high/too low answers allow.
+    OutOfBounds {
+        bound: String,
+        feedback: Feedback,
+    },
+    Cooldown(Duration),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Solved(answer) => write!(f, "already solved with {}", answer),
+            Self::AlreadyRejected(feedback) => write!(f, "already submitted, it was {}", feedback),
+            Self::OutOfBounds { bound, feedback } => {
+                write!(f, "can't be right, {} was {}", bound, feedback)
+            }
+            Self::Cooldown(wait) => write!(f, "wait {}s before submitting again", wait.as_secs()),
+        }
+    }
+}
+
+/// Every submission we made, so known wrong answers are never sent twice
+/// and the site's cooldown is respected without asking it.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    /// Unix time in seconds before which nothing may be submitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_until: Option<u64>,
+    #[serde(default, rename = "attempt")]
+    pub attempts: Vec<Attempt>,
+}
+
+impl History {
+    /// A missing file is an empty history.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => {
+                toml::from_str(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let text = toml::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, text)
+    }
+
+    fn attempts(&self, day: u8, part: u8) -> impl Iterator<Item = &Attempt> + Clone {
+        self.attempts
+            .iter()
+            .filter(move |a| a.day == day && a.part == part)
+    }
+
+    /// Whether `answer` may be submitted at `now`.
+    pub fn check(&self, day: u8, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        let attempts = self.attempts(day, part);
+        if let Some(correct) = attempts.clone().find(|a| a.feedback == Feedback::Correct) {
+            return Err(Refusal::Solved(correct.answer.clone()));
+        }
+        let checked =
+            |a: &&Attempt| !matches!(a.feedback, Feedback::TooRecent | Feedback::WrongLevel);
+        if let Some(known) = attempts
+            .clone()
+            .filter(checked)
+            .find(|a| a.answer == answer)
+        {
+            return Err(Refusal::AlreadyRejected(known.feedback));
+        }
+        if let Ok(value) = answer.parse::<i128>() {
+            let out_of_bounds =
+                attempts
+                    .clone()
+                    .find(|a| match (a.feedback, a.answer.parse::<i128>()) {
+                        (Feedback::TooHigh, Ok(bound)) => value >= bound,
+                        (Feedback::TooLow, Ok(bound)) => value <= bound,
+                        _ => false,
+                    });
+            if let Some(a) = out_of_bounds {
+                return Err(Refusal::OutOfBounds {
+                    bound: a.answer.clone(),
+                    feedback: a.feedback,
+                });
+            }
+        }
+        match self.wait_until {
+            Some(until) if until > now => Err(Refusal::Cooldown(Duration::from_secs(until - now))),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn record(&mut self, day: u8, part: u8, answer: &str, reply: &Reply, now: u64) {
+        self.attempts.push(Attempt {
+            day,
+            part,
+            answer: answer.to_string(),
+            feedback: reply.feedback,
+            at: now,
+        });
+        if let Some(wait) = reply.wait {
+            self.wait_until = Some(now + wait.as_secs());
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Refused(Refusal),
+    Site(SiteError),
+    /// The site replied with something we don't understand.
+    UnknownReply(String),
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Refused(refusal) => write!(f, "not submitted, {}", refusal),
+            Self::Site(err) => write!(f, "{}", err),
+            Self::UnknownReply(body) => write!(f, "unexpected reply: {}", body.trim()),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+/// Checks the history, submits and records the reply in `history`.
+pub fn submit<C: HttpClient>(
+    site: &Site<C>,
+    history: &mut History,
+    day: u8,
+    part: u8,
+    answer: &str,
+    now: u64,
+) -> Result<Reply, SubmitError> {
+    history
+        .check(day, part, answer, now)
+        .map_err(SubmitError::Refused)?;
+    let html = site.submit(day, part, answer).map_err(SubmitError::Site)?;
+    let reply = Reply::parse(&html).ok_or(SubmitError::UnknownReply(html))?;
+    history.record(day, part, answer, &reply, now);
+    Ok(reply)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{client::UreqClient, mock::MockServer};
+
+    use super::*;
+
+    const CORRECT: &str = "<article><p>That's the right answer!  You are one gold star closer to restoring snow operations.</p></article>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again. [<a href=\"/2023/day/1\">Return to Day 1</a>]</p></article>";
+    const TOO_RECENT: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. [<a href=\"/2023/day/1\">Return to Day 1</a>]</p></article>";
+    const WRONG_LEVEL: &str = "<article><p>You don't seem to be solving the right level.  Did you already complete it? [<a href=\"/2023/day/1\">Return to Day 1</a>]</p></article>";
+
+    fn reply(feedback: Feedback, wait: Option<u64>) -> Reply {
+        Reply {
+            feedback,
+            wait: wait.map(Duration::from_secs),
+        }
+    }
+
+    #[test]
+    fn test_parse_reply() {
+        assert_eq!(Reply::parse(CORRECT), Some(reply(Feedback::Correct, None)));
+        assert_eq!(
+            Reply::parse(TOO_HIGH),
+            Some(reply(Feedback::TooHigh, Some(60)))
+        );
+        assert_eq!(
+            Reply::parse(TOO_RECENT),
+            Some(reply(Feedback::TooRecent, Some(65)))
+        );
+        assert_eq!(
+            Reply::parse(WRONG_LEVEL),
+            Some(reply(Feedback::WrongLevel, None))
+        );
+        assert_eq!(
+            Reply::parse(
+                "That's not the right answer.  Please wait 5 minutes before trying again."
+            ),
+            Some(reply(Feedback::Wrong, Some(300)))
+        );
+        assert_eq!(Reply::parse("<html>maintenance</html>"), None);
+    }
+
+    #[test]
+    fn test_check() {
+        let mut history = History::default();
+        assert_eq!(history.check(1, 1, "100", 0), Ok(()));
+
+        history.record(1, 1, "100", &reply(Feedback::TooHigh, Some(60)), 1000);
+        assert_eq!(
+            history.check(1, 1, "50", 1030),
+            Err(Refusal::Cooldown(Duration::from_secs(30)))
+        );
+        assert_eq!(history.check(1, 1, "50", 1060), Ok(()));
+        assert_eq!(
+            history.check(1, 1, "100", 2000),
+            Err(Refusal::AlreadyRejected(Feedback::TooHigh))
+        );
+        assert_eq!(
+            history.check(1, 1, "120", 2000),
+            Err(Refusal::OutOfBounds {
+                bound: "100".to_string(),
+                feedback: Feedback::TooHigh
+            })
+        );
+        // other parts are unaffected
+        assert_eq!(history.check(1, 2, "100", 2000), Ok(()));
+
+        // an answer that wasn't checked may be sent again
+        history.record(1, 1, "40", &reply(Feedback::TooRecent, Some(10)), 2000);
+        assert_eq!(history.check(1, 1, "40", 2010), Ok(()));
+
+        history.record(1, 1, "40", &reply(Feedback::Correct, None), 2010);
+        assert_eq!(
+            history.check(1, 1, "41", 3000),
+            Err(Refusal::Solved("40".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_history_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("submissions.toml");
+        assert_eq!(History::load(&path).unwrap(), History::default());
+
+        let mut history = History::default();
+        history.record(3, 2, "467835", &reply(Feedback::TooLow, Some(60)), 10);
+        history.save(&path).unwrap();
+
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.contains("[[attempt]]"));
+        assert!(text.contains("feedback = \"too_low\""));
+        assert_eq!(History::load(&path).unwrap(), history);
+    }
+
+    #[test]
+    fn test_submit() {
+        let server = MockServer::start(|req| match (req.path.as_str(), req.body.as_str()) {
+            ("/2023/day/1/answer", "level=1&answer=142") => (200, CORRECT.to_string()),
+            ("/2023/day/1/answer", _) => (200, TOO_HIGH.to_string()),
+            _ => (404, "404 Not Found".to_string()),
+        });
+        let site = Site::new(UreqClient::default(), &server.url, "cookie");
+        let mut history = History::default();
+
+        let res = submit(&site, &mut history, 1, 1, "500", 0).unwrap();
+        assert_eq!(res, reply(Feedback::TooHigh, Some(60)));
+        assert!(matches!(
+            submit(&site, &mut history, 1, 1, "142", 30),
+            Err(SubmitError::Refused(Refusal::Cooldown(_)))
+        ));
+        let res = submit(&site, &mut history, 1, 1, "142", 60).unwrap();
+        assert_eq!(res.feedback, Feedback::Correct);
+
+        let received = server.received();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0].method, "POST");
+        assert_eq!(received[0].cookie.as_deref(), Some("session=cookie"));
+        assert_eq!(received[1].body, "level=1&answer=142");
+        assert_eq!(history.attempts.len(), 2);
+    }
+}
@@ -1,36 +1,76 @@
-use std::collections::HashMap;
+use aoc_core::{Answer, ParseError, Solution};
+use tracing::trace;
 
-pub fn process_1(input: &str) -> String {
-    input
-        .lines()
-        .map(|line| {
+// what counts as a digit in part 2, spelled out or not
+const DIGITS: [&str; 18] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "1", "2", "3", "4",
+    "5", "6", "7", "8", "9",
+];
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input
+            .lines()
+            .map(
+                |line| match line.find(|c: char| !c.is_ascii_alphanumeric()) {
+                    Some(idx) => Err(ParseError::new(
+                        Self::DAY,
+                        input,
+                        &line[idx..],
+                        "a letter or digit",
+                    )),
+                    // no calibration value in either part
+                    None if !DIGITS.iter().any(|digit| line.contains(digit)) => Err(
+                        ParseError::new(Self::DAY, input, line, "a digit, spelled out or not"),
+                    ),
+                    None => Ok(line),
+                },
+            )
+            .collect()
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        process_1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        process_2(input).into()
+    }
+}
+
+// part 1 doesn't read spelled out digits, a line with only those adds nothing
+fn process_1(lines: &[&str]) -> u32 {
+    lines
+        .iter()
+        .filter_map(|line| {
             let digits = line
                 .chars()
                 .filter(|c| c.is_numeric())
                 .map(|d| d.to_digit(10).unwrap())
                 .collect::<Vec<u32>>();
-            digits.first().unwrap() * 10 + digits.last().unwrap()
+            Some(digits.first()? * 10 + digits.last()?)
         })
         .sum::<u32>()
-        .to_string()
 }
 
-pub fn process_2(input: &str) -> String {
-    let dig_words = vec![
-        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "1", "2", "3", "4",
-        "5", "6", "7", "8", "9",
-    ];
-    input
-        .lines()
+fn process_2(lines: &[&str]) -> u32 {
+    lines
+        .iter()
         .map(|line| {
             let mut v = Vec::new();
-            for word in dig_words.clone() {
+            for word in DIGITS {
                 line.match_indices(word).for_each(|(idx, _)| {
                     v.push((idx, word));
                 });
             }
 
-            v.sort_by(|(idx_a, _), (idx_b, _)| idx_a.cmp(idx_b));
+            v.sort_by_key(|(idx_a, _)| *idx_a);
 
             let digits = v
                 .into_iter()
@@ -47,38 +87,28 @@ pub fn process_2(input: &str) -> String {
                     x => x.parse::<u32>().unwrap(),
                 })
                 .collect::<Vec<u32>>();
-            dbg!(line, &digits);
+            trace!(line, ?digits);
 
             digits.first().unwrap() * 10 + digits.last().unwrap()
         })
         .sum::<u32>()
-        .to_string()
 }
 
+aoc_core::examples!(Day01);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_process_1() {
-        let input = "1abc2
-pqr3stu8vwx
-a1b2c3d4e5f
-treb7uchet";
-        let res = process_1(input);
-        assert_eq!("142", res);
-    }
-
-    #[test]
-    fn test_process_2() {
-        let input = "two1nine
-eightwothree
-abcone2threexyz
-xtwone3four
-4nineeightseven2
-zoneight234
-7pqrstsixteen";
-        let res = process_2(input);
-        assert_eq!("281", res);
+    fn test_parse_error() {
+        let err = Day01::parse("1abc2\npqrstu\ntreb7uchet").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "a digit, spelled out or not");
+        assert_eq!(err.found, "pqrstu");
+        // enough for part 2, part 1 skips it
+        let input = Day01::parse("1abc2\nsixteen").unwrap();
+        assert_eq!(Day01::part1(&input), Answer::from(12));
+        assert_eq!(Day01::part2(&input), Answer::from(78));
     }
 }
@@ -1,6 +1,17 @@
-use day_02::process_2;
+use std::process;
+
+use aoc_core::{input::Args, Solution};
+use clap::Parser;
+use day_02::Day02;
 
 fn main() {
-    let res = process_2(include_str!("input.txt"));
-    println!("{}", res);
+    let args = Args::parse();
+    let input = args.input(Day02::DAY);
+    match Day02::solve_with(&input, 2, &args.options()) {
+        Ok(res) => println!("{}", res),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
 }
@@ -1,9 +1,18 @@
+use aoc_core::{
+    parse::{parse_lines, IResult},
+    Answer, ParseError, Solution,
+};
 use nom::{
-    branch::alt, bytes::complete::tag, character::complete::digit1, multi::separated_list1, IResult,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::digit1,
+    combinator::{cut, map_res},
+    error::context,
+    multi::separated_list1,
 };
 
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     id: usize,
     subsets: Vec<(usize, usize, usize)>,
 }
@@ -16,20 +25,23 @@ enum Cube {
 }
 
 fn parse_cube(input: &str) -> IResult<&str, Cube> {
-    let (input, amount) = digit1(input)?;
+    let (input, amount) = map_res(digit1, str::parse)(input)?;
     let (input, _) = tag(" ")(input)?;
-    let (input, color) = alt((tag("red"), tag("green"), tag("blue")))(input)?;
+    let (input, color) = context(
+        "red, green or blue",
+        alt((tag("red"), tag("green"), tag("blue"))),
+    )(input)?;
     let cube = match color {
-        "red" => Cube::Red(amount.parse().unwrap()),
-        "green" => Cube::Green(amount.parse().unwrap()),
-        "blue" => Cube::Blue(amount.parse().unwrap()),
+        "red" => Cube::Red(amount),
+        "green" => Cube::Green(amount),
+        "blue" => Cube::Blue(amount),
         _ => unreachable!(),
     };
     Ok((input, cube))
 }
 
 fn parse_set(input: &str) -> IResult<&str, (usize, usize, usize)> {
-    let (input, cubes) = separated_list1(tag(", "), parse_cube)(input)?;
+    let (input, cubes) = separated_list1(tag(", "), cut(parse_cube))(input)?;
     let mut res = (0, 0, 0);
     for cube in cubes {
         match cube {
@@ -42,34 +54,40 @@ fn parse_set(input: &str) -> IResult<&str, (usize, usize, usize)> {
 }
 
 fn parse_game(input: &str) -> IResult<&str, Game> {
-    let (input, _) = tag("Game ")(input)?;
-    let (input, id) = digit1(input)?;
-    let (input, _) = tag(": ")(input)?;
-    let (input, subsets) = separated_list1(tag("; "), parse_set)(input)?;
-
-    Ok((
-        input,
-        Game {
-            id: id.parse().unwrap(),
-            subsets,
-        },
-    ))
+    let (input, _) = context("\"Game \"", tag("Game "))(input)?;
+    let (input, id) = map_res(digit1, str::parse)(input)?;
+    let (input, _) = context("\": \"", tag(": "))(input)?;
+    let (input, subsets) = separated_list1(tag("; "), cut(parse_set))(input)?;
+
+    Ok((input, Game { id, subsets }))
 }
 
-pub fn process_1(input: &str) -> String {
-    let max = (12, 13, 14);
+pub struct Day02;
 
-    let games: Vec<Game> = input
-        .lines()
-        .map(|line| {
-            let (_, game) = parse_game(line).unwrap();
-            game
-        })
-        .collect();
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input<'a> = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_lines(Self::DAY, input, input, parse_game)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        process_1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        process_2(input).into()
+    }
+}
+
+fn process_1(games: &[Game]) -> usize {
+    let max = (12, 13, 14);
 
     let mut res = 0;
 
-    for game in &games {
+    for game in games {
         let test = game
             .subsets
             .iter()
@@ -79,41 +97,32 @@ pub fn process_1(input: &str) -> String {
         }
     }
 
-    res.to_string()
+    res
 }
 
-pub fn process_2(input: &str) -> String {
-    input
-        .lines()
-        .map(|line| {
-            let (_, game) = parse_game(line).unwrap();
+fn process_2(games: &[Game]) -> usize {
+    games
+        .iter()
+        .map(|game| {
             let max_r = game.subsets.iter().map(|(r, _, _)| r).max().unwrap();
             let max_g = game.subsets.iter().map(|(_, g, _)| g).max().unwrap();
             let max_b = game.subsets.iter().map(|(_, _, b)| b).max().unwrap();
             max_r * max_g * max_b
         })
         .sum::<usize>()
-        .to_string()
 }
 
+aoc_core::examples!(Day02);
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-
-    #[test]
-    fn test_process_1() {
-        let res = process_1(INPUT);
-        assert_eq!("8", res);
-    }
 
     #[test]
-    fn test_process_2() {
-        let res = process_2(INPUT);
-        assert_eq!("2286", res);
+    fn test_parse_error() {
+        let err = Day02::parse("Game 1: 3 blue, 4 red\nGame 2: 1 blue, 2 purple").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 19));
+        assert_eq!(err.expected, "red, green or blue");
+        assert_eq!(err.found, "purple");
     }
 }
@@ -1,7 +1,17 @@
-use day_03::process_1;
+use std::process;
+
+use aoc_core::{input::Args, Solution};
+use clap::Parser;
+use day_03::Day03;
 
 fn main() {
-    let input = include_str!("input.txt");
-    let result = process_1(input);
-    println!("{}", result);
+    let args = Args::parse();
+    let input = args.input(Day03::DAY);
+    match Day03::solve_with(&input, 1, &args.options()) {
+        Ok(res) => println!("{}", res),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
 }
@@ -1,161 +1,106 @@
-use std::collections::BTreeMap;
+use std::collections::{HashMap, HashSet};
 
-use nom::{
-    branch::alt,
-    character::complete::{anychar, digit1},
-    multi::many1,
-    IResult,
-};
-
-use itertools::Itertools;
+use aoc_core::{Answer, ParseError, Solution};
+use aoc_grid::{Grid, Pos};
 
+/// A part number, its digits run to the right from `start`.
 #[derive(Debug, Clone)]
-enum Symbol {
-    Dot,
-    Number(u32),
-    Symbol(char),
+pub struct Number {
+    value: u32,
+    start: Pos,
+    len: usize,
 }
 
-fn number(input: &str) -> IResult<&str, Symbol> {
-    let (input, n) = digit1(input)?;
-    Ok((input, Symbol::Number(n.parse().unwrap())))
-}
+impl Number {
+    fn cells(&self) -> impl Iterator<Item = Pos> + '_ {
+        let (x, y) = self.start;
+        (x..x + self.len).map(move |x| (x, y))
+    }
 
-fn other_symbol(input: &str) -> IResult<&str, Symbol> {
-    let (input, c) = anychar(input)?;
-    match c {
-        '.' => Ok((input, Symbol::Dot)),
-        _ => Ok((input, Symbol::Symbol(c))),
+    fn adjacent<'a>(&'a self, grid: &'a Grid<char>) -> impl Iterator<Item = Pos> + 'a {
+        self.cells().flat_map(|pos| grid.neighbors8(pos))
     }
 }
 
-fn line_parse(input: &str) -> IResult<&str, Vec<Symbol>> {
-    let (_, symbols) = many1(alt((number, other_symbol)))(input)?;
-    Ok((input, symbols))
+pub struct Schematic {
+    grid: Grid<char>,
+    numbers: Vec<Number>,
 }
 
-pub fn process_1(input: &str) -> String {
-    let lines = input
-        .lines()
-        .map(|line| {
-            let (_, symbols) = line_parse(line).unwrap();
-            symbols
-        })
-        .collect_vec();
-
-    let mut map: BTreeMap<(usize, usize), Symbol> = BTreeMap::new();
-    for (y, line) in lines.iter().enumerate() {
-        let mut padding = 0;
-        for (x, symbol) in line.iter().enumerate() {
-            map.insert((x + padding, y), symbol.clone());
-            if let Symbol::Number(n) = symbol {
-                padding += n.to_string().len() - 1;
+fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_ascii_digit()
+}
+
+fn find_numbers(grid: &Grid<char>) -> Vec<Number> {
+    let mut numbers = vec![];
+    for (y, row) in grid.rows().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            let digits = row[x..].iter().take_while(|c| c.is_ascii_digit());
+            let len = digits.clone().count();
+            if len == 0 {
+                x += 1;
+                continue;
             }
+            let value = digits.fold(0, |n, d| n * 10 + d.to_digit(10).unwrap());
+            numbers.push(Number {
+                value,
+                start: (x, y),
+                len,
+            });
+            x += len;
         }
     }
-
-    map.clone()
-        .into_iter()
-        .filter_map(|((x, y), symbol)| match symbol {
-            Symbol::Number(n) => {
-                let size = n.to_string().len();
-                let x_range = ((x as i32 - 1).max(0) as usize)..=(x + size);
-                let y_range = ((y as i32 - 1).max(0) as usize)..=(y + 1);
-                for (x_new, y_new) in x_range.cartesian_product(y_range) {
-                    if let Some(Symbol::Symbol(_)) = map.get(&(x_new, y_new)) {
-                        return Some(n);
-                    }
-                }
-
-                None
-            }
-            _ => None,
-        })
-        .sum::<u32>()
-        .to_string()
+    numbers
 }
 
-pub fn process_2(input: &str) -> String {
-    let lines = input
-        .lines()
-        .map(|line| {
-            let (_, symbols) = line_parse(line).unwrap();
-            symbols
-        })
-        .collect_vec();
-
-    let mut map: BTreeMap<(usize, usize), Symbol> = BTreeMap::new();
-    for (y, line) in lines.iter().enumerate() {
-        let mut padding = 0;
-        for (x, symbol) in line.iter().enumerate() {
-            map.insert((x + padding, y), symbol.clone());
-            if let Symbol::Number(n) = symbol {
-                padding += n.to_string().len() - 1;
-            }
-        }
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Input<'a> = Schematic;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let grid = Grid::parse(Self::DAY, input, input, "any character", Some)?;
+        let numbers = find_numbers(&grid);
+        Ok(Schematic { grid, numbers })
     }
 
-    map.clone()
-        .into_iter()
-        .filter_map(|((x, y), symbol)| match symbol {
-            Symbol::Symbol('*') => {
-                let mut parts_nearby = vec![];
-
-                let x_range = (x - 1)..=(x + 1);
-                for new_y in ((y as i32 - 1).max(0) as usize)..=((y + 1).min(lines.len() - 1)) {
-                    let mut padding = 0;
-                    lines
-                        .get(new_y)
-                        .unwrap()
-                        .into_iter()
-                        .enumerate()
-                        .for_each(|(new_x, s)| {
-                            match s {
-                                Symbol::Number(n) => {
-                                    let size = n.to_string().len();
-                                    if ((new_x + padding)..=(new_x + padding + size - 1)).any(|x| x_range.contains(&x)) {
-                                        parts_nearby.push(n);
-                                    }
-                                    padding += size - 1;
-                                },
-                                _ => ()
-                            }
-                        });
-                }
-
-                if parts_nearby.len() == 2 {
-                    return Some(parts_nearby[0] * parts_nearby[1]);
-                }
-
-                None
-            }
-            _ => None,
-        })
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        process_1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        process_2(input).into()
+    }
+}
+
+fn process_1(Schematic { grid, numbers }: &Schematic) -> u32 {
+    numbers
+        .iter()
+        .filter(|number| number.adjacent(grid).any(|pos| is_symbol(grid[pos])))
+        .map(|number| number.value)
         .sum::<u32>()
-        .to_string()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..";
-
-    #[test]
-    fn test_one() {
-        assert_eq!(process_1(INPUT), "4361");
+fn process_2(Schematic { grid, numbers }: &Schematic) -> u32 {
+    let mut gears: HashMap<Pos, Vec<u32>> = HashMap::new();
+    for number in numbers {
+        let stars = number
+            .adjacent(grid)
+            .filter(|pos| grid[*pos] == '*')
+            .collect::<HashSet<_>>();
+        for star in stars {
+            gears.entry(star).or_default().push(number.value);
+        }
     }
 
-    #[test]
-    fn test_two() {
-        assert_eq!(process_2(INPUT), "467835");
-    }
+    gears
+        .values()
+        .filter(|parts| parts.len() == 2)
+        .map(|parts| parts[0] * parts[1])
+        .sum::<u32>()
 }
+
+aoc_core::examples!(Day03);
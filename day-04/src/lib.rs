@@ -1,37 +1,35 @@
+use aoc_core::{
+    parse::{parse_lines, IResult},
+    Answer, ParseError, Solution,
+};
+use aoc_parse::{unsigned, unsigned_list};
 use nom::{
     bytes::complete::tag,
-    character::complete::{digit1, line_ending, multispace1},
-    multi::separated_list1,
-    sequence::{separated_pair, terminated, delimited},
-    IResult,
+    character::complete::space1,
+    error::context,
+    sequence::{delimited, separated_pair, terminated},
 };
 
 #[derive(Debug)]
-struct Card {
+pub struct Card {
     id: u32,
     winning: Vec<u32>,
     chosen: Vec<u32>,
 }
 
-fn number(input: &str) -> IResult<&str, Vec<u32>> {
-    let (input, numbers) = separated_list1(multispace1, digit1)(input)?;
-    let numbers = numbers
-        .iter()
-        .map(|n| n.parse::<u32>().unwrap())
-        .collect::<Vec<u32>>();
-
-    Ok((input, numbers))
-}
-
 // Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
 fn line(input: &str) -> IResult<&str, Card> {
-    let (input, _) = tag("Card")(input)?;
-    let (input, _) = multispace1(input)?;
-    let (input, id) = terminated(digit1, tag(":"))(input)?;
-    let (input, _) = multispace1(input)?;
-    let (input, card) = separated_pair(number, delimited(multispace1, tag("|"), multispace1), number)(input)?;
+    let (input, _) = context("\"Card\"", tag("Card"))(input)?;
+    let (input, _) = space1(input)?;
+    let (input, id) = terminated(unsigned::<u32>, context("\":\"", tag(":")))(input)?;
+    let (input, _) = space1(input)?;
+    let (input, card) = separated_pair(
+        unsigned_list,
+        delimited(space1, context("\"|\"", tag("|")), space1),
+        unsigned_list,
+    )(input)?;
     let card = Card {
-        id: id.parse::<u32>().unwrap() - 1,
+        id: id.saturating_sub(1),
         winning: card.0,
         chosen: card.1,
     };
@@ -39,13 +37,27 @@ fn line(input: &str) -> IResult<&str, Card> {
     Ok((input, card))
 }
 
-fn cards(input: &str) -> IResult<&str, Vec<Card>> {
-    let (input, games) = separated_list1(line_ending, line)(input)?;
-    Ok((input, games))
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input<'a> = Vec<Card>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_lines(Self::DAY, input, input, line)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        process_1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        process_2(input).into()
+    }
 }
 
-pub fn process_1(input: &str) -> String {
-    let (_, games) = cards(input).unwrap();
+fn process_1(games: &[Card]) -> u32 {
     games
         .iter()
         .filter_map(|g| {
@@ -53,14 +65,12 @@ pub fn process_1(input: &str) -> String {
             if amount == 0 {
                 return None;
             }
-            Some((2 as u32).pow(amount as u32 - 1))
+            Some(2u32.pow(amount as u32 - 1))
         })
         .sum::<u32>()
-        .to_string()
 }
 
-pub fn process_2(input: &str) -> String {
-    let (_, cards) = cards(input).unwrap();
+fn process_2(cards: &[Card]) -> u32 {
     let mut no_cards = vec![1; cards.len()];
     cards.iter().for_each(|g| {
         let amount = g.chosen.iter().filter(|c| g.winning.contains(c)).count();
@@ -70,34 +80,7 @@ pub fn process_2(input: &str) -> String {
             }
         }
     });
-    no_cards.iter().sum::<u32>().to_string()
+    no_cards.iter().sum::<u32>()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_process_1() {
-        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        let res = process_1(input);
-        assert_eq!("13", res);
-    }
-
-    #[test]
-    fn test_process_2() {
-        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        let res = process_2(input);
-        assert_eq!("30", res);
-    }
-}
+aoc_core::examples!(Day04);
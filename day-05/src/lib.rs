@@ -1,181 +1,138 @@
 use nom::{
     bytes::complete::tag,
-    character::complete::{alpha1, digit1, newline, space1},
-    multi::separated_list1,
+    character::complete::{alpha1, line_ending, space1},
+    combinator::consumed,
+    error::context,
     sequence::{preceded, separated_pair, terminated, tuple},
-    IResult,
 };
 
-use indicatif::ParallelProgressIterator;
-use rayon::prelude::*;
+use aoc_core::{
+    parse::{parse_all, IResult},
+    Answer, ParseError, Solution,
+};
+use aoc_interval::{Interval, IntervalSet, RangeMap};
+use aoc_parse::{blank_line, labelled, lines, sections, unsigned, unsigned_list};
 
 #[derive(Debug)]
-struct Map {
-    ranges: Vec<(u32, u32, u32)>,
+pub struct Map {
+    ranges: RangeMap<u64>,
 }
 
-impl Map {
-    fn find_next(self: &Self, from: &u32) -> u32 {
-        for (dest, src, len) in &self.ranges {
-            if from >= src && from < &(src + len) {
-                let diff = from - src;
-                return *dest + diff;
-            }
-        }
-        *from
-    }
-}
+// the line along with the range, to point at it if the range overlaps another
+type Range<'a> = (&'a str, (u32, u32, u32));
 
-fn parse_range(input: &str) -> IResult<&str, (u32, u32, u32)> {
-    let (input, first) = digit1(input).map(|(a, b)| (a, b.parse().unwrap()))?;
-    let (input, second) =
-        preceded(tag(" "), digit1)(input).map(|(a, b)| (a, b.parse().unwrap()))?;
-    let (input, third) = preceded(tag(" "), digit1)(input).map(|(a, b)| (a, b.parse().unwrap()))?;
-    Ok((input, (first, second, third)))
+fn parse_range(input: &str) -> IResult<&str, Range<'_>> {
+    consumed(tuple((
+        unsigned,
+        preceded(space1, unsigned),
+        preceded(space1, unsigned),
+    )))(input)
 }
 
-fn parse_map(input: &str) -> IResult<&str, Map> {
+fn parse_map(input: &str) -> IResult<&str, Vec<Range<'_>>> {
     let (input, (_, _)) = terminated(
-        separated_pair(alpha1, tag("-to-"), alpha1),
-        tuple((tag(" map:"), newline)),
+        separated_pair(alpha1, context("\"-to-\"", tag("-to-")), alpha1),
+        tuple((context("\" map:\"", tag(" map:")), line_ending)),
     )(input)?;
-    let (input, ranges) = separated_list1(newline, parse_range)(input)?;
-    Ok((input, Map { ranges }))
+    lines(parse_range)(input)
 }
 
-fn parse_input(input: &str) -> IResult<&str, (Vec<u32>, Vec<Map>)> {
-    let mut groups = input.split("\n\n");
-    let (_, seeds) =
-        preceded(tag("seeds: "), separated_list1(space1, digit1))(groups.next().unwrap())?;
-    let seeds = seeds.iter().map(|x| x.parse().unwrap()).collect();
+// the seeds come with their line, to point past the last one if it has no length
+type Seeds<'a> = (&'a str, Vec<u32>);
 
-    let maps = groups.map(|m| parse_map(m).unwrap().1).collect::<Vec<_>>();
+fn parse_input(input: &str) -> IResult<&str, (Seeds<'_>, Vec<Vec<Range<'_>>>)> {
+    let (input, seeds) = consumed(labelled("seeds", unsigned_list))(input)?;
+    let (input, maps) = preceded(blank_line, sections(parse_map))(input)?;
 
     Ok((input, (seeds, maps)))
 }
 
-pub fn process_1(input: &str) -> String {
-    let (_, (seeds, maps)) = parse_input(input).unwrap();
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input<'a> = (Vec<u32>, Vec<Map>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let ((line, seeds), maps) = parse_all(Self::DAY, input, parse_input)?;
+        if seeds.len() % 2 != 0 {
+            let end = &line[line.len()..];
+            let expected = "the length of the last seed range";
+            return Err(ParseError::new(Self::DAY, input, end, expected));
+        }
+        let maps = maps
+            .into_iter()
+            .map(|ranges| {
+                let mut map = RangeMap::new();
+                for (line, (dest, src, len)) in ranges {
+                    let source = Interval::with_len(src.into(), len.into());
+                    map.try_insert(source, dest.into()).map_err(|other| {
+                        let expected =
+                            format!("a range clear of the source {}..{}", other.start, other.end);
+                        ParseError::new(Self::DAY, input, line, expected)
+                    })?;
+                }
+                Ok(Map { ranges: map })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok((seeds, maps))
+    }
+
+    fn part1((seeds, maps): &Self::Input<'_>) -> Answer {
+        process_1(seeds, maps).into()
+    }
+
+    fn part2((seeds, maps): &Self::Input<'_>) -> Answer {
+        process_2(seeds, maps).into()
+    }
+}
+
+fn process_1(seeds: &[u32], maps: &[Map]) -> u64 {
     seeds
         .iter()
         .map(|seed| {
-            let mut seed = *seed;
-            for map in &maps {
-                seed = map.find_next(&seed);
-            }
-            seed
+            maps.iter()
+                .fold(u64::from(*seed), |seed, map| map.ranges.get(seed))
         })
         .min()
         .unwrap()
-        .to_string()
 }
 
-pub fn process_2(input: &str) -> String {
-    let (_, (seeds, maps)) = parse_input(input).unwrap();
+// the seeds come in ranges, which are mapped whole
+fn process_2(seeds: &[u32], maps: &[Map]) -> u64 {
     let seeds = seeds
-        .chunks(2)
-        .flat_map(|x| {
-            let start = x[0];
-            let stop = x[1];
-            start..(start + stop)
-        })
-        .collect::<Vec<u32>>();
+        .chunks_exact(2)
+        .map(|x| Interval::with_len(x[0].into(), x[1].into()))
+        .collect::<IntervalSet<u64>>();
 
-    // parallelize it lol
-    seeds
-        .par_iter()
-        .progress()
-        .map(|seed| {
-            let mut seed = *seed;
-            for map in &maps {
-                seed = map.find_next(&seed);
-            }
-            seed
-        })
+    maps.iter()
+        .fold(seeds, |seeds, map| map.ranges.map(&seeds))
         .min()
         .unwrap()
-        .to_string()
 }
 
+aoc_core::examples!(Day05);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_process_1() {
-        let input = "seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4";
-        let res = process_1(input);
-        assert_eq!("35", res);
+    fn test_overlapping_ranges() {
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50 48\n60 90 10\n";
+        let err = Day05::parse(input).unwrap_err();
+        assert_eq!((err.line, err.column), (6, 1));
+        assert_eq!(err.expected, "a range clear of the source 50..98");
+        assert_eq!(err.found, "60 90 10");
     }
 
     #[test]
-    fn test_process_2() {
-        let input = "seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4";
-        let res = process_2(input);
-        assert_eq!("46", res);
+    fn test_odd_seeds() {
+        let input = "seeds: 79 14 55\n\nseed-to-soil map:\n50 98 2\n";
+        let err = Day05::parse(input).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 16));
+        assert_eq!(err.expected, "the length of the last seed range");
+        assert_eq!(err.found, "");
     }
 }
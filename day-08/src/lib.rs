@@ -1,45 +1,103 @@
-use std::collections::{BTreeMap, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 
+use aoc_core::{
+    cycle::Cycle,
+    math::crt,
+    parse::{parse_all, IResult},
+    Answer, ParseError, Solution,
+};
 use nom::{
-    bytes::complete::tag,
-    character::complete::{alpha1, alphanumeric1, newline},
+    bytes::complete::{is_a, tag},
+    character::complete::{alphanumeric1, line_ending},
+    error::context,
     multi::separated_list1,
-    sequence::{delimited, separated_pair, terminated},
-    IResult,
+    sequence::{delimited, pair, separated_pair, terminated},
 };
-use num::Integer;
+use tracing::debug;
 
 #[derive(Debug)]
-struct Instruction<'a> {
+pub struct Instruction<'a> {
     left: &'a str,
     right: &'a str,
 }
 
-fn parse_instruction(input: &str) -> IResult<&str, (&str, Instruction)> {
-    let (input, from) = terminated(alphanumeric1, tag(" = "))(input)?;
+fn parse_instruction(input: &str) -> IResult<&str, (&str, Instruction<'_>)> {
+    let (input, from) = terminated(alphanumeric1, context("\" = \"", tag(" = ")))(input)?;
     let (input, (left, right)) = delimited(
-        tag("("),
-        separated_pair(alphanumeric1, tag(", "), alphanumeric1),
-        tag(")"),
+        context("\"(\"", tag("(")),
+        separated_pair(alphanumeric1, context("\", \"", tag(", ")), alphanumeric1),
+        context("\")\"", tag(")")),
     )(input)?;
     Ok((input, (from, Instruction { left, right })))
 }
 
-fn parse_input(input: &str) -> IResult<&str, (VecDeque<char>, BTreeMap<&str, Instruction>)> {
-    let (input, chars) = alpha1(input)?;
+type Network<'a> = (VecDeque<char>, BTreeMap<&'a str, Instruction<'a>>);
+
+fn parse_input(input: &str) -> IResult<&str, Network<'_>> {
+    let (input, chars) = context("L or R", is_a("LR"))(input)?;
     let chars = chars.chars().collect::<VecDeque<_>>();
 
-    let (input, _) = newline(input)?;
-    let (input, _) = newline(input)?;
+    let (input, _) = pair(line_ending, line_ending)(input)?;
 
-    let (input, instructions) = separated_list1(newline, parse_instruction)(input)?;
+    let (input, instructions) = separated_list1(line_ending, parse_instruction)(input)?;
 
     Ok((input, (chars, instructions.into_iter().collect())))
 }
 
-pub fn process_1(input: &str) -> String {
-    let (_, (mut chars, instructions)) = parse_input(input).unwrap();
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Input<'a> = Network<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let network = parse_all(Self::DAY, input, parse_input)?;
+        // both parts start from one, AAA for part 1 and all of them for part 2
+        if !network.1.keys().any(|node| node.ends_with('A')) {
+            let end = input.trim_end().len();
+            return Err(ParseError::at_offset(
+                Self::DAY,
+                input,
+                end,
+                "a node ending in A",
+            ));
+        }
+        Ok(network)
+    }
+
+    fn part1((chars, instructions): &Self::Input<'_>) -> Answer {
+        process_1(chars.clone(), instructions).into()
+    }
+
+    fn part2((chars, instructions): &Self::Input<'_>) -> Answer {
+        match process_2(chars, instructions) {
+            Some(steps) => steps.into(),
+            None => "the ghosts are never on a Z together".into(),
+        }
+    }
 
+    // part 1 follows the walk from AAA, part 2 the ghosts from every node ending in A
+    #[cfg(feature = "visualize")]
+    fn export(
+        (chars, instructions): &Self::Input<'_>,
+        part: u8,
+        path: &std::path::Path,
+    ) -> Option<std::io::Result<()>> {
+        let starts = instructions
+            .keys()
+            .copied()
+            .filter(|node| match part {
+                1 => *node == "AAA",
+                _ => node.ends_with('A'),
+            })
+            .collect::<Vec<_>>();
+        let dot = network_dot(chars, instructions, &starts);
+        Some(std::fs::write(path, dot))
+    }
+}
+
+fn process_1(mut chars: VecDeque<char>, instructions: &BTreeMap<&str, Instruction>) -> u64 {
     let mut cur = "AAA";
     let mut counter = 0;
 
@@ -53,100 +111,239 @@ pub fn process_1(input: &str) -> String {
             _ => panic!("huh"),
         };
         if cur == "ZZZ" {
-            return counter.to_string();
+            return counter;
         }
         chars.push_back(next_hop);
     }
 }
 
-fn traverse(
-    start: &str,
-    instructions: &BTreeMap<&str, Instruction>,
-    mut chars: VecDeque<char>,
-) -> u64 {
-    let mut counter = 0;
+// a ghost is somewhere in the network at some point in the instructions
+type Ghost<'a> = (&'a str, usize);
 
-    let mut cur = start;
-
-    loop {
-        counter += 1;
-        let next_hop = chars.pop_front().unwrap();
+// the walk of a single ghost, which ends up going round in circles
+fn walk<'a>(
+    start: &'a str,
+    instructions: &BTreeMap<&'a str, Instruction<'a>>,
+    chars: &VecDeque<char>,
+) -> Cycle<Ghost<'a>> {
+    Cycle::find((start, 0), |&(cur, i)| {
         let instruction = instructions.get(cur).unwrap();
-        cur = match next_hop {
+        let next = match chars[i] {
             'R' => instruction.right,
             'L' => instruction.left,
             _ => panic!("huh"),
         };
-        if cur.ends_with("Z") {
-            return counter;
-        }
-        chars.push_back(next_hop);
-    }
+        (next, (i + 1) % chars.len())
+    })
+}
+
+fn at_z(walk: &Cycle<Ghost>, steps: usize) -> bool {
+    walk.state_after(steps).0.ends_with('Z')
 }
 
-pub fn process_2(input: &str) -> String {
-    let (_, (chars, instructions)) = parse_input(input).unwrap();
+// the steps into its cycle at which a ghost is on a Z
+fn z_offsets(walk: &Cycle<Ghost>) -> Vec<usize> {
+    (walk.start..walk.start + walk.len)
+        .filter(|&steps| at_z(walk, steps))
+        .collect()
+}
 
-    let mut solutions = instructions
+// The ghosts meet on the first step where all of them stand on a Z. Until the
+// last one is in its cycle that is checked step by step, after that every
+// ghost is on a Z at some offsets into its cycle and CRT lines those up.
+fn process_2(chars: &VecDeque<char>, instructions: &BTreeMap<&str, Instruction>) -> Option<u64> {
+    let walks = instructions
         .keys()
-        .filter(|x| x.ends_with("A"))
-        .map(|start| traverse(start, &instructions, chars.clone())).collect::<Vec<_>>();
+        .filter(|x| x.ends_with('A'))
+        .map(|start| {
+            let walk = walk(start, instructions, chars);
+            debug!(
+                start,
+                cycle_start = walk.start,
+                cycle_len = walk.len,
+                "ghost"
+            );
+            walk
+        })
+        .collect::<Vec<_>>();
+
+    let settled = walks.iter().map(|walk| walk.start).max().unwrap_or(0);
+    if let Some(steps) = (1..settled).find(|&steps| walks.iter().all(|walk| at_z(walk, steps))) {
+        return Some(steps as u64);
+    }
 
-    solutions.sort();
+    // the steps at which the ghosts so far are all on a Z, one congruence for
+    // every way of lining up their offsets that doesn't contradict itself. The
+    // ones left all have the lcm of the cycles so far as their modulus, so the
+    // set collapses them to at most one per step of that combined cycle where
+    // those ghosts are all on a Z, rather than one per choice of offsets.
+    let aligned = walks
+        .iter()
+        .fold(BTreeSet::from([(0, 1)]), |aligned, walk| {
+            let offsets = z_offsets(walk);
+            aligned
+                .iter()
+                .flat_map(|&congruence| {
+                    offsets.iter().filter_map(move |&steps| {
+                        crt([congruence, (steps as i128, walk.len as i128)])
+                    })
+                })
+                .collect()
+        });
 
-    let mut res = 1;
-    for sol in &solutions {
-        let tmp = res.lcm(sol);
-        println!("lcm({}, {}) = {}", sol, res, tmp);
-        res = tmp;
+    aligned
+        .into_iter()
+        .map(|(r, m)| {
+            // the first match once every ghost is in its cycle
+            let settled = settled.max(1) as i128;
+            if r >= settled {
+                r
+            } else {
+                r + (settled - r + m - 1) / m * m
+            }
+        })
+        .min()
+        .map(|steps| steps as u64)
+}
+
+// The network as a Graphviz graph, with every edge labelled with the instruction
+// taking it. Nodes ending in A and Z are filled in, and the cycle each ghost
+// from `starts` ends up in is drawn in a colour of its own.
+#[cfg(feature = "visualize")]
+fn network_dot(
+    chars: &VecDeque<char>,
+    instructions: &BTreeMap<&str, Instruction>,
+    starts: &[&str],
+) -> String {
+    use std::{collections::HashMap, fmt::Write};
+
+    const COLORS: [&str; 8] = [
+        "crimson",
+        "royalblue",
+        "forestgreen",
+        "darkorange",
+        "purple",
+        "deeppink",
+        "teal",
+        "goldenrod",
+    ];
+
+    // the colours of the ghosts whose cycle goes through a node or takes an edge
+    let mut nodes: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut edges: HashMap<(&str, char), Vec<&str>> = HashMap::new();
+    let mut legend = String::new();
+    for (start, color) in starts.iter().zip(COLORS.iter().cycle()) {
+        let walk = walk(start, instructions, chars);
+        for steps in walk.start..walk.start + walk.len {
+            let (node, i) = *walk.state_after(steps);
+            for colors in [
+                nodes.entry(node).or_default(),
+                edges.entry((node, chars[i])).or_default(),
+            ] {
+                if !colors.contains(color) {
+                    colors.push(color);
+                }
+            }
+        }
+        let _ = write!(
+            legend,
+            "{}: {}, cycle of {} from step {}, on Z at {:?}\\l",
+            start,
+            color,
+            walk.len,
+            walk.start,
+            z_offsets(&walk)
+        );
     }
 
-    res.to_string()
+    let mut dot = String::new();
+    dot.push_str("digraph network {\n");
+    let _ = writeln!(dot, "  label=\"{}\";", legend);
+    dot.push_str("  labeljust=l;\n");
+    dot.push_str("  node [shape=ellipse, style=filled, fillcolor=white];\n");
+    for (node, instruction) in instructions {
+        let mut attrs = vec![];
+        if node.ends_with('A') {
+            attrs.push("fillcolor=palegreen".to_string());
+        } else if node.ends_with('Z') {
+            attrs.push("fillcolor=lightsalmon".to_string());
+        }
+        if let Some(colors) = nodes.get(node) {
+            attrs.push(format!("color=\"{}\", penwidth=2", colors.join(":")));
+        }
+        if attrs.is_empty() {
+            let _ = writeln!(dot, "  \"{}\";", node);
+        } else {
+            let _ = writeln!(dot, "  \"{}\" [{}];", node, attrs.join(", "));
+        }
+
+        // one edge for both turns when they lead to the same node
+        let turns: &[(&str, &[char])] = if instruction.left == instruction.right {
+            &[(instruction.left, &['L', 'R'])]
+        } else {
+            &[(instruction.left, &['L']), (instruction.right, &['R'])]
+        };
+        for (to, labels) in turns {
+            let mut colors = vec![];
+            for label in labels.iter() {
+                for color in edges.get(&(*node, *label)).into_iter().flatten() {
+                    if !colors.contains(color) {
+                        colors.push(*color);
+                    }
+                }
+            }
+            let label = labels.iter().map(char::to_string).collect::<Vec<_>>();
+            let mut attrs = vec![format!("label=\"{}\"", label.join(","))];
+            if !colors.is_empty() {
+                attrs.push(format!("color=\"{}\", penwidth=2", colors.join(":")));
+            }
+            let _ = writeln!(dot, "  \"{}\" -> \"{}\" [{}];", node, to, attrs.join(", "));
+        }
+    }
+    dot.push_str("}\n");
+    dot
 }
 
+aoc_core::examples!(Day08);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_process_1() {
-        let input = "RL
-
-AAA = (BBB, CCC)
-BBB = (DDD, EEE)
-CCC = (ZZZ, GGG)
-DDD = (DDD, DDD)
-EEE = (EEE, EEE)
-GGG = (GGG, GGG)
-ZZZ = (ZZZ, ZZZ)";
-        let res = process_1(input);
-        assert_eq!("2", res);
+    fn test_no_ghosts() {
+        let err = Day08::parse("LR\n\nBBB = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)\n").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 17));
+        assert_eq!(err.expected, "a node ending in A");
     }
 
+    // one ghost is on a Z after every odd number of steps, the other after
+    // every even one
     #[test]
-    fn test_process_1_2() {
-        let input = "LLR
-
-AAA = (BBB, BBB)
-BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ)";
-        let res = process_1(input);
-        assert_eq!("6", res);
+    fn test_never_together() {
+        let input = "L\n\n11A = (11Z, 11Z)\n11Z = (11B, 11B)\n11B = (11Z, 11Z)\n\
+                     22A = (22B, 22B)\n22B = (22Z, 22Z)\n22Z = (22B, 22B)\n";
+        let network = Day08::parse(input).unwrap();
+        assert_eq!(
+            Day08::part2(&network),
+            Answer::from("the ghosts are never on a Z together")
+        );
     }
 
+    #[cfg(feature = "visualize")]
     #[test]
-    fn test_process_2() {
-        let input = "LR
-
-11A = (11B, XXX)
-11B = (XXX, 11Z)
-11Z = (11B, XXX)
-22A = (22B, XXX)
-22B = (22C, 22C)
-22C = (22Z, 22Z)
-22Z = (22B, 22B)
-XXX = (XXX, XXX)";
-        let res = process_2(input);
-        assert_eq!("6", res);
+    fn test_network_dot() {
+        let (chars, instructions) = Day08::parse(include_str!("../examples/ghosts.in")).unwrap();
+        let dot = network_dot(&chars, &instructions, &["11A", "22A"]);
+        assert!(dot.starts_with("digraph network {\n"));
+        assert!(dot.contains(r#"11A: crimson, cycle of 2 from step 1, on Z at [2]\l"#));
+        assert!(dot.contains(r#"22A: royalblue, cycle of 6 from step 1, on Z at [3, 6]\l"#));
+        assert!(dot.contains(r#""11A" [fillcolor=palegreen];"#));
+        assert!(dot.contains(r#""11A" -> "11B" [label="L"];"#));
+        assert!(dot.contains(r#""11B" -> "11Z" [label="R", color="crimson", penwidth=2];"#));
+        assert!(dot.contains(r#""11Z" [fillcolor=lightsalmon, color="crimson", penwidth=2];"#));
+        assert!(dot.contains(r#""22B" -> "22C" [label="L,R", color="royalblue", penwidth=2];"#));
+        assert!(dot.contains(r#""XXX" -> "XXX" [label="L,R"];"#));
+        assert!(dot.contains("  \"XXX\";\n"));
     }
 }
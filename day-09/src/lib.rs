@@ -1,21 +1,27 @@
-use nom::{
-    character::complete::{self, newline, space1},
-    multi::separated_list1,
-    IResult,
-};
+use aoc_core::{parse::parse_lines, Answer, ParseError, Solution};
+use aoc_parse::signed_list;
 
-fn parse_line(input: &str) -> IResult<&str, Vec<i64>> {
-    let (input, nums) = separated_list1(space1, complete::i64)(input)?;
-    Ok((input, nums))
-}
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Input<'a> = Vec<Vec<i64>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_lines(Self::DAY, input, input, signed_list)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        process_1(input).into()
+    }
 
-fn parse_input(input: &str) -> IResult<&str, Vec<Vec<i64>>> {
-    let (input, lines) = separated_list1(newline, parse_line)(input)?;
-    Ok((input, lines))
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        process_2(input).into()
+    }
 }
 
-pub fn process_1(input: &str) -> String {
-    let (_, lines) = parse_input(input).unwrap();
+fn process_1(lines: &[Vec<i64>]) -> i64 {
     lines
         .iter()
         .map(|line| {
@@ -32,11 +38,11 @@ pub fn process_1(input: &str) -> String {
             for i in 1..(size) {
                 let mut fin = true;
                 for j in i..(size) {
-                    let left = matrix.get(i - 1).unwrap().get(j - 1).unwrap().clone();
-                    let right = matrix.get(i - 1).unwrap().get(j).unwrap().clone();
+                    let left = *matrix.get(i - 1).unwrap().get(j - 1).unwrap();
+                    let right = *matrix.get(i - 1).unwrap().get(j).unwrap();
                     let val = matrix.get_mut(i).unwrap().get_mut(j).unwrap();
                     *val = right - left;
-                    if val != & 0 {
+                    if val != &0 {
                         fin = false;
                     }
                 }
@@ -49,16 +55,14 @@ pub fn process_1(input: &str) -> String {
             let mut res = 0;
             for i in (1..=end_idx).rev() {
                 let left = matrix.get(i - 1).unwrap().last().unwrap();
-                res = res + left;
+                res += left;
             }
             res
         })
         .sum::<i64>()
-        .to_string()
 }
 
-pub fn process_2(input: &str) -> String {
-    let (_, lines) = parse_input(input).unwrap();
+fn process_2(lines: &[Vec<i64>]) -> i64 {
     lines
         .iter()
         .map(|line| {
@@ -74,12 +78,14 @@ pub fn process_2(input: &str) -> String {
 
             for i in 1..(size) {
                 let mut fin = true;
-                for j in 0..(size-i) {
-                    let left = matrix.get(i - 1).unwrap().get(j).unwrap().clone();
-                    let right = matrix.get(i - 1).unwrap().get(j + 1).unwrap().clone();
+                for j in 0..(size - i) {
+                    let left = *matrix.get(i - 1).unwrap().get(j).unwrap();
+                    let right = *matrix.get(i - 1).unwrap().get(j + 1).unwrap();
                     let value = matrix.get_mut(i).unwrap().get_mut(j).unwrap();
                     *value = right - left;
-                    if value != &0 { fin = false; }
+                    if value != &0 {
+                        fin = false;
+                    }
                 }
                 if fin {
                     end_idx = i;
@@ -97,28 +103,6 @@ pub fn process_2(input: &str) -> String {
             res
         })
         .sum::<i64>()
-        .to_string()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_process_1() {
-        let input = "0 3 6 9 12 15
-1 3 6 10 15 21
-10 13 16 21 30 45";
-        let res = process_1(input);
-        assert_eq!("114", res);
-    }
-
-    #[test]
-    fn test_process_2() {
-        let input = "0 3 6 9 12 15
-1 3 6 10 15 21
-10 13 16 21 30 45";
-        let res = process_2(input);
-        assert_eq!("2", res);
-    }
-}
+aoc_core::examples!(Day09);
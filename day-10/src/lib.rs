@@ -1,21 +1,8 @@
-use petgraph::{Graph, Undirected};
-use std::collections::HashMap;
+use aoc_core::{Answer, ParseError, Solution};
+use aoc_grid::{Direction, Grid, Point, Polygon, Pos};
 
-#[derive(Debug, PartialEq, Eq)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
-
-struct Flow {
-    one: Direction,
-    two: Direction,
-}
-
-#[derive(Debug, PartialEq, Eq)]
-enum Pipe {
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Pipe {
     Vertical,
     Horizontal,
     NE,
@@ -27,9 +14,9 @@ enum Pipe {
 }
 
 impl Pipe {
-    fn new(c: char) -> Self {
+    fn new(c: char) -> Option<Self> {
         use Pipe::*;
-        match c {
+        Some(match c {
             '|' => Vertical,
             '-' => Horizontal,
             'L' => NE,
@@ -38,162 +25,92 @@ impl Pipe {
             'F' => SE,
             '.' => Ground,
             'S' => Start,
-            _ => panic!("Did not match {}", c),
-        }
-    }
-
-    fn get_flow(&self) -> Flow {
-        use Direction::*;
-        use Pipe::*;
-        match self {
-            Vertical => Flow {
-                one: North,
-                two: South,
-            },
-            Horizontal => Flow {
-                one: East,
-                two: West,
-            },
-            NE => Flow {
-                one: North,
-                two: East,
-            },
-            NW => Flow {
-                one: North,
-                two: West,
-            },
-            SW => Flow {
-                one: South,
-                two: West,
-            },
-            SE => Flow {
-                one: South,
-                two: East,
-            },
-            _ => panic!(":("),
-        }
+            _ => return None,
+        })
     }
 
-    fn connects_to(&self, other: &Self, xdiff: i8, ydiff: i8) -> bool {
+    // the sides the pipe leads out of, the start's aren't known
+    fn openings(&self) -> Option<[Direction; 2]> {
         use Direction::*;
         use Pipe::*;
-        if self == &Start || other == &Start {
-            return true;
-        }
-        let self_flow = self.get_flow();
-        let other_flow = other.get_flow();
-        todo!();
+        Some(match self {
+            Vertical => [North, South],
+            Horizontal => [East, West],
+            NE => [North, East],
+            NW => [North, West],
+            SW => [South, West],
+            SE => [South, East],
+            Ground | Start => return None,
+        })
     }
 }
 
-pub fn process_1(input: &str) -> String {
-    let mut map: HashMap<(u32, u32), (Pipe, _)> = HashMap::new();
-    let mut graph: Graph<(u32, u32), u8, Undirected> = Graph::new_undirected();
-
-    let mut height: u32 = 0;
-    let mut width: u32 = 0;
+pub struct Day10;
 
-    input.lines().enumerate().for_each(|(line_idx, line)| {
-        let mut tmp_width = 0;
-        line.chars().enumerate().for_each(|(char_idx, c)| {
-            let index = graph.add_node((char_idx as u32, line_idx as u32));
-            map.insert((char_idx as u32, line_idx as u32), (Pipe::new(c), index));
-            tmp_width += 1;
-        });
-
-        if tmp_width > width {
-            width = tmp_width;
-        }
-        height += 1;
-    });
-
-    for (pos, (pipe, idx)) in &map {
-        use Pipe::*;
-        if (pos == &(0, 0) && pipe != &SE)
-            || (pos == &(width - 1, 0) && pipe != &SW)
-            || (pos == &(0, height - 1) && pipe != &NE)
-            || (pos == &(width - 1, height - 1) && pipe != &NW)
-            || pipe == &Ground
-        {
-            continue;
-        }
+impl Solution for Day10 {
+    const DAY: u8 = 10;
 
-        let (x, y) = pos;
+    type Input<'a> = Grid<Pipe>;
 
-        if (*x == 0 && (pipe == &SW || pipe == &Horizontal || pipe == &NW))
-            || (*y == 0 && (pipe == &Vertical || pipe == &NE || pipe == &NW))
-        {
-            continue;
-        }
-
-        let to_check = match pipe {
-            Vertical => vec![(*x, y + 1), (*x, y - 1)],
-            Horizontal => vec![(x + 1, *y), (x - 1, *y)],
-            NE => vec![(*x, y - 1), (x + 1, *y)],
-            NW => vec![(*x, y - 1), (x - 1, *y)],
-            SW => vec![(x - 1, *y), (*x, y + 1)],
-            SE => vec![(x + 1, *y), (*x, y + 1)],
-            _ => vec![],
-        };
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Grid::parse(Self::DAY, input, input, "a pipe, '.' or 'S'", Pipe::new)
+    }
 
-        let mut valid = true;
-        for pos_new in to_check {
-            let other_opt = map.get(&pos_new);
-            if !match other_opt {
-                Some((other, _)) => pipe.connects_to(
-                    other,
-                    pos.0 as i8 - pos_new.0 as i8,
-                    pos.1 as i8 - pos_new.1 as i8,
-                ),
-                None => false,
-            } {
-                valid = false;
-            }
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        process_1(input)
+    }
 
-            if !valid {
-                break;
-            }
-        }
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        process_2(input)
     }
-    todo!();
 }
 
-pub fn process_2(_input: &str) -> String {
-    todo!();
+// the farthest tile from the start is halfway around the loop
+fn process_1(pipes: &Grid<Pipe>) -> Answer {
+    (find_loop(pipes).len() / 2).into()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_process_1_1() {
-        let input = "-L|F7
-7S-7|
-L|7||
--L-J|
-L|-JF";
-        let res = process_1(input);
-        assert_eq!("4", res);
+// the tiles passed following the pipes from `start` towards `dir`, if
+// that leads back to the start
+fn walk(pipes: &Grid<Pipe>, start: Pos, mut dir: Direction) -> Option<Vec<Pos>> {
+    let mut tiles = vec![start];
+    let mut pos = pipes.step(start, dir)?;
+    while pos != start {
+        tiles.push(pos);
+        let back = dir.opposite();
+        dir = match pipes[pos].openings()? {
+            [one, two] if one == back => two,
+            [one, two] if two == back => one,
+            _ => return None,
+        };
+        pos = pipes.step(pos, dir)?;
     }
+    Some(tiles)
+}
 
-    #[test]
-    #[ignore]
-    fn test_process_1_2() {
-        let input = "7-F7-
-.FJ|7
-SJLL7
-|F--J
-LJ.LJ";
-        let res = process_1(input);
-        assert_eq!("8", res);
-    }
+// the tiles of the loop through the start, in the order they are passed
+fn find_loop(pipes: &Grid<Pipe>) -> Vec<Pos> {
+    let start = pipes
+        .find(|pipe| *pipe == Pipe::Start)
+        .expect("there is a start");
+    // stray pipes can lead into the start as well, only the loop comes back
+    Direction::ALL
+        .into_iter()
+        .find_map(|dir| walk(pipes, start, dir))
+        .expect("no loop through the start")
+}
 
-    #[test]
-    #[ignore]
-    fn test_process_2() {
-        let input = "";
-        let res = process_2(input);
-        assert_eq!("", res);
-    }
+// the loop runs through the middle of its tiles, which makes the enclosed
+// tiles the lattice points inside it
+fn process_2(pipes: &Grid<Pipe>) -> Answer {
+    let tiles = find_loop(pipes);
+    let polygon = Polygon::new(
+        tiles
+            .into_iter()
+            .map(|pos| Point::from(pos).signed())
+            .collect(),
+    );
+    polygon.interior_points().into()
 }
+
+aoc_core::examples!(Day10);
@@ -1,6 +1,17 @@
-use day_11::process_2;
+use std::process;
+
+use aoc_core::{input::Args, Solution};
+use clap::Parser;
+use day_11::Day11;
 
 fn main() {
-    let res = process_2(include_str!("input.txt"), 1_000_000);
-    println!("{}", res);
+    let args = Args::parse();
+    let input = args.input(Day11::DAY);
+    match Day11::solve_with(&input, 2, &args.options()) {
+        Ok(res) => println!("{}", res),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
 }
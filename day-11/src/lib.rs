@@ -1,144 +1,108 @@
-use std::collections::HashSet;
 use itertools::*;
+use std::collections::HashSet;
 
+use aoc_core::{Answer, ParseError, Solution};
+use aoc_grid::{Grid, Point};
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
-struct Point {
-    x: usize,
-    y: usize,
+pub struct Image {
+    galaxies: Vec<Point<usize>>,
+    empty_rows: Vec<usize>,
+    empty_cols: Vec<usize>,
 }
 
-pub fn process_1(input: &str) -> String {
-    let width = input.lines().next().unwrap().len();
-    let mut empty_cols = vec![true; width];
-    let mut empty_rows = Vec::new();
-    let mut galaxies = Vec::new();
-    input.lines().enumerate().for_each(|(line_idx, line)| {
-        let mut is_empty = true;
-        line.chars()
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input<'a> = Image;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let image = Grid::parse(Self::DAY, input, input, "'.' or '#'", |c| match c {
+            '.' => Some(false),
+            '#' => Some(true),
+            _ => None,
+        })?;
+        let galaxies = image
+            .iter()
+            .filter(|(_, galaxy)| **galaxy)
+            .map(|(pos, _)| Point::from(pos))
+            .collect();
+        let empty_rows = image
+            .rows()
             .enumerate()
-            .for_each(|(char_idx, char)| match char {
-                '.' => (),
-                '#' => {
-                    is_empty = false;
-                    empty_cols[char_idx] = false;
-                    galaxies.push(Point {
-                        x: char_idx,
-                        y: line_idx,
-                    });
-                }
-                _ => panic!(":("),
-            });
-        if is_empty {
-            empty_rows.push(line_idx);
-        }
-    });
-
-    let empty_cols = empty_cols
-        .iter()
-        .enumerate()
-        .filter_map(|(i, x)| match x {
-            true => Some(i),
-            false => None,
-        })
-        .collect::<Vec<_>>();
+            .filter_map(|(y, row)| (!row.contains(&true)).then_some(y))
+            .collect();
+        let empty_cols = image
+            .columns()
+            .enumerate()
+            .filter_map(|(x, mut col)| (!col.any(|galaxy| *galaxy)).then_some(x))
+            .collect();
 
-    let galaxies = galaxies.iter().map(|Point { mut x, mut y }| {
-        x += empty_cols.iter().filter(|c| c < &&x).count();
-        y += empty_rows.iter().filter(|r| r < &&y).count();
-        Point { x, y }
-    }).collect::<Vec<_>>();
+        Ok(Image {
+            galaxies,
+            empty_rows,
+            empty_cols,
+        })
+    }
 
-    let pairs = galaxies.into_iter().combinations(2).map(|mut v| {
-        v.sort();
-        (v[0].clone(), v[1].clone())
-    }).collect::<HashSet<_>>();
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        process(input, 2).into()
+    }
 
-    pairs.iter().map(|(a, b)| {
-        (a.x as i64 - b.x as i64).abs() + (a.y as i64 - b.y as i64).abs()
-    }).sum::<i64>().to_string()
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        process(input, 1_000_000).into()
+    }
 }
 
-pub fn process_2(input: &str, dx: usize) -> String {
-    let width = input.lines().next().unwrap().len();
-    let mut empty_cols = vec![true; width];
-    let mut empty_rows = Vec::new();
-    let mut galaxies = Vec::new();
-    input.lines().enumerate().for_each(|(line_idx, line)| {
-        let mut is_empty = true;
-        line.chars()
-            .enumerate()
-            .for_each(|(char_idx, char)| match char {
-                '.' => (),
-                '#' => {
-                    is_empty = false;
-                    empty_cols[char_idx] = false;
-                    galaxies.push(Point {
-                        x: char_idx,
-                        y: line_idx,
-                    });
-                }
-                _ => panic!(":("),
-            });
-        if is_empty {
-            empty_rows.push(line_idx);
-        }
-    });
-
-    let empty_cols = empty_cols
+// every empty row and column is replaced by `dx` of them
+fn process(image: &Image, dx: usize) -> i64 {
+    let Image {
+        galaxies,
+        empty_rows,
+        empty_cols,
+    } = image;
+
+    let galaxies = galaxies
         .iter()
-        .enumerate()
-        .filter_map(|(i, x)| match x {
-            true => Some(i),
-            false => None,
+        .map(|Point { mut x, mut y }| {
+            x += empty_cols.iter().filter(|c| c < &&x).count() * (dx - 1);
+            y += empty_rows.iter().filter(|r| r < &&y).count() * (dx - 1);
+            Point::new(x, y)
         })
         .collect::<Vec<_>>();
 
-    let galaxies = galaxies.iter().map(|Point { mut x, mut y }| {
-        x += empty_cols.iter().filter(|c| c < &&x).count() * (dx - 1);
-        y += empty_rows.iter().filter(|r| r < &&y).count() * (dx - 1);
-        Point { x, y }
-    }).collect::<Vec<_>>();
-
-    let pairs = galaxies.into_iter().combinations(2).map(|mut v| {
-        v.sort();
-        (v[0].clone(), v[1].clone())
-    }).collect::<HashSet<_>>();
+    let pairs = galaxies
+        .into_iter()
+        .combinations(2)
+        .map(|mut v| {
+            v.sort();
+            (v[0], v[1])
+        })
+        .collect::<HashSet<_>>();
 
-    pairs.iter().map(|(a, b)| {
-        (a.x as i64 - b.x as i64).abs() + (a.y as i64 - b.y as i64).abs()
-    }).sum::<i64>().to_string()
+    pairs
+        .iter()
+        .map(|(a, b)| a.manhattan(*b) as i64)
+        .sum::<i64>()
 }
 
+aoc_core::examples!(Day11);
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "...#......
-.......#..
-#.........
-..........
-......#...
-.#........
-.........#
-..........
-.......#..
-#...#.....";
-
-    #[test]
-    fn test_process_1() {
-        let res = process_1(INPUT);
-        assert_eq!("374", res);
-    }
+    const INPUT: &str = include_str!("../examples/example.in");
 
     #[test]
     fn test_process_2_1() {
-        let res = process_2(INPUT, 10);
-        assert_eq!("1030", res);
+        let res = process(&Day11::parse(INPUT).unwrap(), 10);
+        assert_eq!(1030, res);
     }
-    
+
     #[test]
     fn test_process_2_2() {
-        let res = process_2(INPUT, 100);
-        assert_eq!("8410", res);
+        let res = process(&Day11::parse(INPUT).unwrap(), 100);
+        assert_eq!(8410, res);
     }
 }
@@ -1,16 +1,23 @@
-use std::{collections::HashMap, fmt::Display};
+use std::fmt::Display;
 
+use aoc_core::{
+    memo::Memo,
+    parse::{parse_lines, IResult},
+    Answer, ParseError, Solution,
+};
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{self, multispace1, newline},
+    character::complete::{self, space1},
+    error::context,
     multi::{many1, separated_list1},
     sequence::separated_pair,
-    IResult, Parser,
+    Parser,
 };
+use tracing::trace;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Copy)]
-enum SpringState {
+pub enum SpringState {
     Operational,
     Damaged,
     Unknown,
@@ -31,213 +38,104 @@ impl Display for SpringState {
 }
 
 fn parse_spring_1(input: &str) -> IResult<&str, Vec<SpringState>> {
-    many1(alt((
-        tag(".").map(|_| SpringState::Operational),
-        tag("#").map(|_| SpringState::Damaged),
-        tag("?").map(|_| SpringState::Unknown),
-    )))(input)
+    many1(context(
+        "'.', '#' or '?'",
+        alt((
+            tag(".").map(|_| SpringState::Operational),
+            tag("#").map(|_| SpringState::Damaged),
+            tag("?").map(|_| SpringState::Unknown),
+        )),
+    ))(input)
 }
 
 fn parse_groups_1(input: &str) -> IResult<&str, Vec<u64>> {
     separated_list1(tag(","), complete::u64)(input)
 }
 
-fn parse_spring_2(input: &str) -> IResult<&str, Vec<SpringState>> {
-    let (input, mut springs) = many1(alt((
-        tag(".").map(|_| SpringState::Operational),
-        tag("#").map(|_| SpringState::Damaged),
-        tag("?").map(|_| SpringState::Unknown),
-    )))(input)?;
-
-    let springs_cp = springs.clone();
-
-    (0..4).for_each(|_| {
-        springs.push(SpringState::Unknown);
-        for ele in springs_cp.iter() {
-            springs.push(ele.clone());
-        }
-    });
-    Ok((input, springs))
+fn parse_row_1(input: &str) -> IResult<&str, Row> {
+    separated_pair(parse_spring_1, space1, parse_groups_1)(input)
 }
 
-fn parse_groups_2(input: &str) -> IResult<&str, Vec<u64>> {
-    let (input, mut groups) = separated_list1(tag(","), complete::u64)(input)?;
+type Row = (Vec<SpringState>, Vec<u64>);
 
+// five copies of the springs joined by unknowns, five copies of the groups
+fn unfold((springs, groups): &Row) -> Row {
+    let mut springs = springs.clone();
+    let mut groups = groups.clone();
+    let springs_cp = springs.clone();
     let groups_cp = groups.clone();
 
     (0..4).for_each(|_| {
-        for ele in groups_cp.iter() {
-            groups.push(ele.clone());
-        }
+        springs.push(SpringState::Unknown);
+        springs.extend(springs_cp.iter());
+        groups.extend(groups_cp.iter());
     });
-    Ok((input, groups))
-}
-
-fn parse_row_1(input: &str) -> IResult<&str, (Vec<SpringState>, Vec<u64>)> {
-    separated_pair(parse_spring_1, multispace1, parse_groups_1)(input)
+    (springs, groups)
 }
 
-fn parse_row_2(input: &str) -> IResult<&str, (Vec<SpringState>, Vec<u64>)> {
-    separated_pair(parse_spring_2, multispace1, parse_groups_2)(input)
-}
-
-fn solve(springs: Vec<SpringState>, groups: Vec<u64>, cache: &mut HashMap<String, u64>) -> u64 {
-    let key = format!(
-        "{}{}",
-        springs
-            .iter()
-            .map(|x| x.to_string())
-            .collect::<Vec<_>>()
-            .join(""),
-        groups
-            .iter()
-            .map(|x| x.to_string())
-            .collect::<Vec<_>>()
-            .join(",")
-    );
-
-    if let Some(value) = cache.get(&key) {
-        return *value;
-    }
-
-    let value = calc(springs.clone(), groups.clone(), cache);
-    cache.insert(key, value);
-    value
-}
-
-fn calc(springs: Vec<SpringState>, groups: Vec<u64>, cache: &mut HashMap<String, u64>) -> u64 {
-    let mut springs = springs;
-    let mut groups = groups;
-
-    loop {
-        // no groups left
-        if groups.len() == 0 {
-            return if springs
-                .iter()
-                .filter(|x| x == &&SpringState::Damaged)
-                .count()
-                > 0
-            {
-                0
-            } else {
-                1
-            };
-        }
-
-        // no springs but still a group
-        if springs.len() == 0 {
+// the ways to fill in the unknown springs so the damaged ones form `groups`, in order
+fn arrangements(
+    springs: &[SpringState],
+    groups: &[u64],
+    memo: &mut Memo<(usize, usize), u64>,
+) -> u64 {
+    // both are what is left of the same row, so their lengths tell them apart
+    memo.get_or_insert_with((springs.len(), groups.len()), |memo| {
+        let Some((&group, rest)) = groups.split_first() else {
+            return u64::from(!springs.contains(&SpringState::Damaged));
+        };
+        let Some(&first) = springs.first() else {
             return 0;
-        }
+        };
 
-        match springs.first().unwrap() {
-            // remove all operational from beginning
-            SpringState::Operational => {
-                springs.remove(0);
-                continue;
-            }
-            // run two instances
-            SpringState::Unknown => {
-                let mut left = springs.clone();
-                let mut right = springs.clone();
-                *left.get_mut(0).unwrap() = SpringState::Operational;
-                *right.get_mut(0).unwrap() = SpringState::Damaged;
-                return solve(left, groups.clone(), cache) + solve(right, groups.clone(), cache);
-            }
-            SpringState::Damaged => {
-                // if there are no enough springs to satisfy group
-                if springs.len() < groups[0] as usize {
-                    return 0;
-                }
-
-                // if there is an operational spring inside group
-                if (0..groups[0])
-                    .map(|x| springs[x as usize])
-                    .filter(|x| x == &SpringState::Operational)
-                    .count()
-                    > 0
-                {
-                    return 0;
-                }
-
-                // if there is more than one group
-                if groups.len() > 1 {
-                    // if there is damaged spring after group
-                    if springs.len() < groups[0] as usize + 1
-                        || springs[groups[0] as usize] == SpringState::Damaged
-                    {
-                        return 0;
-                    }
-
-                    (0..=groups[0]).rev().for_each(|i| {
-                        springs.remove(i as usize);
-                    });
-
-                    groups.remove(0);
-                    continue;
-                }
-
-                (0..groups[0]).rev().for_each(|i| {
-                    springs.remove(i as usize);
-                });
-
-                groups.remove(0);
+        let mut count = 0;
+        if first != SpringState::Damaged {
+            count += arrangements(&springs[1..], groups, memo);
+        }
+        if first != SpringState::Operational {
+            // the group starts here, it needs room and an end that isn't damaged
+            let len = group as usize;
+            let fits = springs.len() >= len
+                && !springs[..len].contains(&SpringState::Operational)
+                && springs.get(len) != Some(&SpringState::Damaged);
+            if fits {
+                let next = (len + 1).min(springs.len());
+                count += arrangements(&springs[next..], rest, memo);
             }
         }
-    }
+        count
+    })
 }
 
-pub fn process_1(input: &str) -> String {
-    let (_, rows) = separated_list1(newline, parse_row_1)(input).unwrap();
-    let mut cache: HashMap<String, u64> = HashMap::new();
-
-    rows.into_iter()
-        .map(|row| solve(row.0, row.1, &mut cache))
-        .sum::<u64>()
-        .to_string()
+fn count((springs, groups): &Row) -> u64 {
+    let mut memo = Memo::new();
+    let count = arrangements(springs, groups, &mut memo);
+    trace!(count, memo = %memo.stats(), "row");
+    count
 }
 
-pub fn process_2(input: &str) -> String {
-    let (_, rows) = separated_list1(newline, parse_row_2)(input).unwrap();
-    let mut cache: HashMap<String, u64> = HashMap::new();
+pub struct Day12;
 
-    rows.into_iter()
-        .map(|row| solve(row.0, row.1, &mut cache))
-        .sum::<u64>()
-        .to_string()
-}
+impl Solution for Day12 {
+    const DAY: u8 = 12;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    type Input<'a> = Vec<Row>;
 
-    #[test]
-    fn test_process_1_1() {
-        let input = "???.### 1,1,3";
-        let res = process_1(input);
-        assert_eq!("1", res);
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_lines(Self::DAY, input, input, parse_row_1)
     }
 
-    #[test]
-    fn test_process_1_2() {
-        let input = "???.### 1,1,3
-.??..??...?##. 1,1,3
-?#?#?#?#?#?#?#? 1,3,1,6
-????.#...#... 4,1,1
-????.######..#####. 1,6,5
-?###???????? 3,2,1";
-        let res = process_1(input);
-        assert_eq!("21", res);
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        input.iter().map(count).sum::<u64>().into()
     }
 
-    #[test]
-    fn test_process_2() {
-        let input = "???.### 1,1,3
-.??..??...?##. 1,1,3
-?#?#?#?#?#?#?#? 1,3,1,6
-????.#...#... 4,1,1
-????.######..#####. 1,6,5
-?###???????? 3,2,1";
-        let res = process_2(input);
-        assert_eq!("525152", res);
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        input
+            .iter()
+            .map(|row| count(&unfold(row)))
+            .sum::<u64>()
+            .into()
     }
 }
+
+aoc_core::examples!(Day12);
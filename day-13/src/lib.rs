@@ -1,22 +1,14 @@
 use itertools::Itertools;
 use std::collections::HashSet;
 
-fn transpose<T>(v: Vec<Vec<T>>) -> Vec<Vec<T>> {
-    assert!(!v.is_empty());
-    let len = v[0].len();
-    let mut iters: Vec<_> = v.into_iter().map(|n| n.into_iter()).collect();
-    (0..len)
-        .map(|_| {
-            iters
-                .iter_mut()
-                .map(|n| n.next().unwrap())
-                .collect::<Vec<T>>()
-        })
-        .collect()
-}
+use aoc_core::{parse::parse_all, Answer, ParseError, Solution};
+use aoc_grid::Grid;
+use aoc_parse::{grid, sections};
 
-fn process_mirror(lines: &Vec<Vec<char>>) -> HashSet<usize> {
-    let mut lines = lines.iter();
+type Pattern = Grid<char>;
+
+fn process_mirror(pattern: &Pattern) -> HashSet<usize> {
+    let mut lines = pattern.rows();
     let first_line = lines.next().unwrap();
     let mut cols_to_check = HashSet::new();
 
@@ -38,178 +30,89 @@ fn process_mirror(lines: &Vec<Vec<char>>) -> HashSet<usize> {
     cols_to_check
 }
 
-pub fn process_1(input: &str) -> String {
-    let patterns = input.split("\n\n");
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input<'a> = Vec<Pattern>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let patterns = parse_all(
+            Self::DAY,
+            input,
+            sections(grid("'.' or '#'", |c| matches!(c, '.' | '#').then_some(c))),
+        )?;
+        Ok(patterns.into_iter().map(Grid::from_rows).collect())
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        process_1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        process_2(input).into()
+    }
+}
+
+fn process_1(patterns: &[Pattern]) -> usize {
     patterns
+        .iter()
         .map(|pattern| {
-            let pattern = pattern
-                .lines()
-                .map(|line| line.chars().collect::<Vec<_>>())
-                .collect::<Vec<_>>();
-
-            let col_mirror = process_mirror(&pattern).iter().sum::<usize>();
-            let t = transpose(pattern);
-            let row_mirror = process_mirror(&t).iter().sum::<usize>();
+            let col_mirror = process_mirror(pattern).iter().sum::<usize>();
+            let row_mirror = process_mirror(&pattern.transpose()).iter().sum::<usize>();
             col_mirror + row_mirror * 100
         })
         .sum::<usize>()
-        .to_string()
 }
 
-pub fn process_2(input: &str) -> String {
-    let patterns = input.split("\n\n");
+fn process_2(patterns: &[Pattern]) -> usize {
     patterns
+        .iter()
         .map(|pattern| {
             // loool brute force 🤓🤓
-            let pattern = pattern
-                .lines()
-                .map(|line| line.chars().collect::<Vec<_>>())
-                .collect::<Vec<_>>();
-
-            let og_col_mirror = process_mirror(&pattern).iter().sum::<usize>();
-            let (og_row_mirror, pattern) = if og_col_mirror == 0 {
-                let t = transpose(pattern);
-                let og_row_mirror = process_mirror(&t).iter().sum::<usize>();
-                let pattern = transpose(t);
-                (og_row_mirror, pattern)
+            let og_col_mirror = process_mirror(pattern).iter().sum::<usize>();
+            let og_row_mirror = if og_col_mirror == 0 {
+                process_mirror(&pattern.transpose()).iter().sum::<usize>()
             } else {
-                (0, pattern)
+                0
             };
 
-            let height = pattern.len();
-            let width = pattern.get(0).unwrap().len();
+            let height = pattern.height();
+            let width = pattern.width();
 
             (0..width)
                 .cartesian_product(0..height)
                 .map(|(x, y)| {
                     let mut tmp_pattern = pattern.clone();
-                    let tmp = *tmp_pattern.get(y).unwrap().get(x).unwrap();
-                    *tmp_pattern.get_mut(y).unwrap().get_mut(x).unwrap() = match tmp {
+                    let tmp = tmp_pattern[(x, y)];
+                    tmp_pattern[(x, y)] = match tmp {
                         '#' => '.',
                         '.' => '#',
                         _ => panic!(),
                     };
-                    assert_ne!(tmp, *tmp_pattern.get(y).unwrap().get(x).unwrap());
+                    assert_ne!(tmp, tmp_pattern[(x, y)]);
 
                     let col_mirror = process_mirror(&tmp_pattern);
                     let col_mirror = col_mirror
                         .into_iter()
-                        .filter(|x| x != &og_col_mirror)
-                        .next()
+                        .find(|x| x != &og_col_mirror)
                         .unwrap_or(0);
 
-                    return if col_mirror != 0 {
+                    if col_mirror != 0 {
                         col_mirror
                     } else {
-                        let t = transpose(tmp_pattern);
-                        let row_mirrors = process_mirror(&t);
+                        let row_mirrors = process_mirror(&tmp_pattern.transpose());
                         100 * row_mirrors
                             .into_iter()
-                            .filter(|x| x != &og_row_mirror)
-                            .next()
+                            .find(|x| x != &og_row_mirror)
                             .unwrap_or(0)
-                    };
-                }).max().unwrap()
+                    }
+                })
+                .max()
+                .unwrap()
         })
         .sum::<usize>()
-        .to_string()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_process_1_1() {
-        let input = "#.##..##.
-..#.##.#.
-##......#
-##......#
-..#.##.#.
-..##..##.
-#.#.##.#.";
-        let res = process_1(input);
-        assert_eq!("5", res);
-    }
-
-    #[test]
-    fn test_process_1_2() {
-        let input = "#...##..#
-#....#..#
-..##..###
-#####.##.
-#####.##.
-..##..###
-#....#..#";
-        let res = process_1(input);
-        assert_eq!("400", res);
-    }
-
-    #[test]
-    fn test_process_1() {
-        let input = "#.##..##.
-..#.##.#.
-##......#
-##......#
-..#.##.#.
-..##..##.
-#.#.##.#.
-
-#...##..#
-#....#..#
-..##..###
-#####.##.
-#####.##.
-..##..###
-#....#..#";
-        let res = process_1(input);
-        assert_eq!("405", res);
-    }
-
-    #[test]
-    fn test_process_2_1() {
-        let input = "#.##..##.
-..#.##.#.
-##......#
-##......#
-..#.##.#.
-..##..##.
-#.#.##.#.";
-        let res = process_2(input);
-        assert_eq!("300", res);
-    }
-
-    #[test]
-    fn test_process_2_2() {
-        let input = "#...##..#
-#....#..#
-..##..###
-#####.##.
-#####.##.
-..##..###
-#....#..#";
-        let res = process_2(input);
-        assert_eq!("100", res);
-    }
-
-    #[test]
-    fn test_process_2() {
-        let input = "#.##..##.
-..#.##.#.
-##......#
-##......#
-..#.##.#.
-..##..##.
-#.#.##.#.
-
-#...##..#
-#....#..#
-..##..###
-#####.##.
-#####.##.
-..##..###
-#....#..#";
-        let res = process_2(input);
-        assert_eq!("400", res);
-    }
-}
+aoc_core::examples!(Day13);
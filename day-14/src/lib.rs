@@ -1,269 +1,146 @@
-use std::collections::{BTreeMap, HashMap};
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Rock {
+use std::fmt::Display;
+
+use aoc_core::{cycle, Answer, ParseError, Solution};
+use aoc_grid::Grid;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Tile {
     Round,
     Cube,
+    Empty,
 }
 
-impl Rock {
+impl Tile {
     fn new(c: char) -> Option<Self> {
         match c {
-            'O' => Some(Rock::Round),
-            '#' => Some(Rock::Cube),
+            'O' => Some(Tile::Round),
+            '#' => Some(Tile::Cube),
+            '.' => Some(Tile::Empty),
             _ => None,
         }
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Tile::Round => 'O',
+            Tile::Cube => '#',
+            Tile::Empty => '.',
+        };
+        write!(f, "{}", c)
+    }
 }
 
-// BTreeMap cuz it sorts by key
-fn parse_input(input: &str) -> (BTreeMap<(usize, usize), Rock>, usize, usize) {
-    let tmp = input.lines().collect::<Vec<_>>();
-    let height = tmp.len();
-    let width = tmp.first().unwrap().chars().collect::<Vec<_>>().len();
-    (
-        input
-            .lines()
-            .enumerate()
-            .flat_map(|(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .filter_map(|(x, c)| {
-                        if let Some(rock) = Rock::new(c) {
-                            Some(((x, y), rock))
-                        } else {
-                            None
-                        }
-                    })
-                    .collect::<Vec<_>>()
-            })
-            .collect(),
-        height,
-        width,
-    )
-}
+type Platform = Grid<Tile>;
 
-pub fn process_1(input: &str) -> String {
-    let (mut rocks, height, _) = parse_input(input);
+pub struct Day14;
 
-    rocks
-        .clone()
-        .into_iter()
-        .for_each(|((x, y), rock)| match rock {
-            Rock::Cube => (),
-            Rock::Round => {
-                if y > 0 {
-                    let mut new_y = None;
-                    for yp in (0..y).rev() {
-                        match rocks.get(&(x, yp)) {
-                            None => (),
-                            Some(_) => {
-                                new_y = Some(yp + 1);
-                                break;
-                            }
-                        }
+impl Solution for Day14 {
+    const DAY: u8 = 14;
 
-                        new_y = Some(0);
-                    }
-                    if let Some(new_y) = new_y {
-                        rocks.remove(&(x, y));
-                        rocks.insert((x, new_y), rock);
-                    }
-                }
-            }
-        });
+    type Input<'a> = Platform;
 
-    rocks
-        .iter()
-        .filter_map(|((_, y), rock)| match rock {
-            Rock::Cube => None,
-            Rock::Round => Some(height - y),
-        })
-        .sum::<usize>()
-        .to_string()
-}
-
-fn grid_key(rocks: &BTreeMap<(usize, usize), Rock>, height: &usize, width: &usize) -> String {
-    let mut out = "".to_string();
-    for y in 0..*height {
-        for x in 0..*width {
-            out += match rocks.get(&(x, y)) {
-                Some(rock) => match rock {
-                    Rock::Round => "O",
-                    Rock::Cube => "#",
-                },
-                None => ".",
-            }
-        }
-        out += "\n";
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Grid::parse(Self::DAY, input, input, "'O', '#' or '.'", Tile::new)
     }
-    out
-}
-
-pub fn process_2(input: &str) -> String {
-    use Direction::*;
-    let (mut rocks, height, width) = parse_input(input);
-    let mut cache: HashMap<String, usize> = HashMap::new();
-    'outer: for i in 1usize..=1_000_000_000 {
-        for direction in vec![North, West, South, East] {
-            if direction == South || direction == East {
-                rocks
-                    .clone()
-                    .into_iter()
-                    .rev()
-                    .for_each(|((x, y), rock)| match rock {
-                        Rock::Cube => (),
-                        Rock::Round => {
-                            let mut new_pos = None;
-                            match direction {
-                                South => {
-                                    for yp in (y + 1)..height {
-                                        match rocks.get(&(x, yp)) {
-                                            None => (),
-                                            Some(_) => {
-                                                new_pos = Some((x, yp - 1));
-                                                break;
-                                            }
-                                        }
-
-                                        new_pos = Some((x, height - 1));
-                                    }
-                                }
-                                East => {
-                                    for xp in (x + 1)..width {
-                                        match rocks.get(&(xp, y)) {
-                                            None => (),
-                                            Some(_) => {
-                                                new_pos = Some((xp - 1, y));
-                                                break;
-                                            }
-                                        }
-
-                                        new_pos = Some((width - 1, y));
-                                    }
-                                }
-                                _ => panic!(),
-                            }
-                            if let Some(new_pos) = new_pos {
-                                rocks.remove(&(x, y));
-                                rocks.insert(new_pos, rock);
-                            }
-                        }
-                    });
-            } else {
-                rocks
-                    .clone()
-                    .into_iter()
-                    .for_each(|((x, y), rock)| match rock {
-                        Rock::Cube => (),
-                        Rock::Round => {
-                            let mut new_pos = None;
-                            match direction {
-                                North => {
-                                    for yp in (0..y).rev() {
-                                        match rocks.get(&(x, yp)) {
-                                            None => (),
-                                            Some(_) => {
-                                                new_pos = Some((x, yp + 1));
-                                                break;
-                                            }
-                                        }
 
-                                        new_pos = Some((x, 0));
-                                    }
-                                }
-                                West => {
-                                    for xp in (0..x).rev() {
-                                        match rocks.get(&(xp, y)) {
-                                            None => (),
-                                            Some(_) => {
-                                                new_pos = Some((xp + 1, y));
-                                                break;
-                                            }
-                                        }
-
-                                        new_pos = Some((0, y));
-                                    }
-                                }
-                                _ => panic!(),
-                            }
-                            if let Some(new_pos) = new_pos {
-                                rocks.remove(&(x, y));
-                                rocks.insert(new_pos, rock);
-                            }
-                        }
-                    });
-            }
-        }
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        process_1(input.clone()).into()
+    }
 
-        let key = grid_key(&rocks, &height, &width);
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        process_2(input.clone()).into()
+    }
 
-        if let Some(cycle_start) = cache.get(&key) {
-            let cycle_size = i - cycle_start;
-            let target_cycle = cycle_start + (1_000_000_000 - cycle_start) % cycle_size;
+    #[cfg(feature = "visualize")]
+    fn record(
+        input: &Self::Input<'_>,
+        part: u8,
+        to: &aoc_core::solution::Recording,
+    ) -> Option<std::io::Result<()>> {
+        Some(record_tilts(input, part).save(to))
+    }
+}
 
-            for (k, v) in &cache {
-                if *v == target_cycle {
-                    let (rocks_tmp, _, _) = parse_input(k);
-                    rocks = rocks_tmp;
-                    break 'outer;
+// rolls every round rock north until it hits a cube, another rock or the edge
+fn tilt_north(rocks: &mut Platform) {
+    for x in 0..rocks.width() {
+        let mut free = 0;
+        for y in 0..rocks.height() {
+            match rocks[(x, y)] {
+                Tile::Cube => free = y + 1,
+                Tile::Round => {
+                    rocks[(x, y)] = Tile::Empty;
+                    rocks[(x, free)] = Tile::Round;
+                    free += 1;
                 }
+                Tile::Empty => (),
             }
-        } else {
-            cache.insert(key, i);
         }
     }
+}
+
+// north, west, south, east: turning clockwise after each tilt brings the
+// next side to the top, four turns bring the platform back the way it was
+fn spin(rocks: &Platform) -> Platform {
+    (0..4).fold(rocks.clone(), |mut rocks, _| {
+        tilt_north(&mut rocks);
+        rocks.rotate_cw()
+    })
+}
 
+fn load(rocks: &Platform) -> usize {
     rocks
         .iter()
-        .filter_map(|((_, y), rock)| match rock {
-            Rock::Cube => None,
-            Rock::Round => Some(height - y),
-        })
+        .filter(|(_, tile)| **tile == Tile::Round)
+        .map(|((_, y), _)| rocks.height() - y)
         .sum::<usize>()
-        .to_string()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn process_1(mut rocks: Platform) -> usize {
+    tilt_north(&mut rocks);
+    load(&rocks)
+}
 
-    #[test]
-    fn test_process_1() {
-        let input = "O....#....
-O.OO#....#
-.....##...
-OO.#O....O
-.O.....O#.
-O.#..O.#.#
-..O..#O..O
-.......O..
-#....###..
-#OO..#....";
-        let res = process_1(input);
-        assert_eq!("136", res);
-    }
+fn process_2(rocks: Platform) -> usize {
+    load(&cycle::state_after(rocks, 1_000_000_000, spin))
+}
 
-    #[test]
-    fn test_process_2() {
-        let input = "O....#....
-O.OO#....#
-.....##...
-OO.#O....O
-.O.....O#.
-O.#..O.#.#
-..O..#O..O
-.......O..
-#....###..
-#OO..#....";
-        let res = process_2(input);
-        assert_eq!("64", res);
+// the platform after every tilt, for part 2 until the spin cycles start repeating
+#[cfg(feature = "visualize")]
+fn record_tilts(rocks: &Platform, part: u8) -> aoc_grid::animation::Recorder {
+    use aoc_grid::{animation::Recorder, render::Rgb};
+
+    const PALETTE: [Rgb; 3] = [Rgb(25, 25, 35), Rgb(120, 120, 130), Rgb(230, 180, 60)];
+    let mut recorder = Recorder::new(rocks.width(), rocks.height())
+        .palette(&PALETTE)
+        .delay_ms(50);
+    let mut frame = |rocks: &Platform| {
+        recorder.record(rocks, |_, tile| match tile {
+            Tile::Empty => 0,
+            Tile::Cube => 1,
+            Tile::Round => 2,
+        })
+    };
+
+    frame(rocks);
+    let mut rocks = rocks.clone();
+    if part == 1 {
+        tilt_north(&mut rocks);
+        frame(&rocks);
+    } else {
+        let cycle = cycle::Cycle::find(rocks.clone(), spin);
+        for _ in 0..cycle.start + cycle.len {
+            for turns in 0..4 {
+                tilt_north(&mut rocks);
+                // turned back the way the input has it
+                frame(&(0..turns).fold(rocks.clone(), |rocks, _| rocks.rotate_ccw()));
+                rocks = rocks.rotate_cw();
+            }
+        }
     }
+    recorder
 }
+
+aoc_core::examples!(Day14);
@@ -1,70 +1,98 @@
 use std::collections::{HashMap, VecDeque};
 
+use aoc_core::{
+    parse::{parse_all, IResult},
+    Answer, ParseError, Solution,
+};
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{self, alpha1},
+    combinator::consumed,
+    error::context,
     multi::separated_list1,
-    sequence::preceded,
-    IResult, Parser,
+    sequence::{pair, preceded},
+    Parser,
 };
 
 #[derive(Debug)]
-enum Operation {
+pub enum Operation {
     Sub,
     Add(u32),
 }
 
 #[derive(Debug)]
-struct Step<'a> {
+pub struct Step<'a> {
+    raw: &'a str,
     label: &'a str,
     operation: Operation,
 }
 
-impl<'a> Step<'a> {
+fn hash(word: &str) -> u32 {
+    let mut res = 0;
+    word.chars().for_each(|c| {
+        let ascii = c as u8;
+        res += ascii as u32;
+        res = (res * 17) % 256;
+    });
+    res
+}
+
+impl Step<'_> {
     fn get_hash(&self) -> u32 {
-        let mut res = 0;
-        self.label.chars().for_each(|c| {
-            let ascii = c as u8;
-            res += ascii as u32;
-            res = (res * 17) % 256;
-        });
-        res
+        hash(self.label)
     }
 }
 
-fn parse_step(input: &str) -> IResult<&str, Step> {
-    let (input, label) = alpha1(input)?;
-    let (input, operation) = alt((
-        tag("-").map(|_| Operation::Sub),
-        preceded(tag("="), complete::u32).map(|x| Operation::Add(x)),
+fn parse_step(input: &str) -> IResult<&str, Step<'_>> {
+    let (input, (raw, (label, operation))) = consumed(pair(
+        alpha1,
+        context(
+            "'-' or '='",
+            alt((
+                tag("-").map(|_| Operation::Sub),
+                preceded(tag("="), complete::u32).map(Operation::Add),
+            )),
+        ),
     ))(input)?;
-    Ok((input, Step { label, operation }))
+    Ok((
+        input,
+        Step {
+            raw,
+            label,
+            operation,
+        },
+    ))
 }
 
-pub fn process_1(input: &str) -> String {
-    input
-        .strip_suffix("\n")
-        .unwrap_or(input)
-        .split(',')
-        .map(|word| {
-            let mut res = 0;
-            word.chars().for_each(|c| {
-                let ascii = c as u8;
-                res += ascii as u32;
-                res = (res * 17) % 256;
-            });
-            res
-        })
-        .sum::<u32>()
-        .to_string()
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input<'a> = Vec<Step<'a>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(Self::DAY, input, separated_list1(tag(","), parse_step))
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        process_1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        process_2(input).into()
+    }
+}
+
+fn process_1(steps: &[Step]) -> u32 {
+    steps.iter().map(|step| hash(step.raw)).sum::<u32>()
 }
 
-pub fn process_2(input: &str) -> String {
-    let (_, steps) = separated_list1(tag(","), parse_step)(input).unwrap();
+fn process_2(steps: &[Step]) -> usize {
     let mut boxes: HashMap<u32, VecDeque<(&str, u32)>> = HashMap::new();
     steps.iter().for_each(|s| {
-        let box_ = boxes.entry(s.get_hash()).or_insert(VecDeque::new());
+        let box_ = boxes.entry(s.get_hash()).or_default();
         match s.operation {
             Operation::Add(v) => {
                 if let Some(index) = box_.iter().position(|x| x.0 == s.label) {
@@ -90,24 +118,6 @@ pub fn process_2(input: &str) -> String {
                 .sum::<usize>()
         })
         .sum::<usize>()
-        .to_string()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_process_1() {
-        let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-        let res = process_1(input);
-        assert_eq!("1320", res);
-    }
-
-    #[test]
-    fn test_process_2() {
-        let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-        let res = process_2(input);
-        assert_eq!("145", res);
-    }
-}
+aoc_core::examples!(Day15);
@@ -1,20 +1,25 @@
 use std::collections::HashMap;
 
+use aoc_core::{Answer, ParseError, Solution};
+use aoc_grid::{Direction, Grid, Point};
+use tracing::{debug, trace};
+
 #[derive(Debug)]
-enum Splitter {
+pub enum Splitter {
     Vertical,   // |
     Horizontal, // -
 }
 
 impl Splitter {
-    fn get_next_dirs(&self, dir: &Direction) -> Option<Vec<Direction>> {
+    fn get_next_dirs(&self, dir: Direction) -> Option<Vec<Direction>> {
+        use Direction::*;
         match self {
             Self::Vertical => match dir {
-                Direction::Left | Direction::Right => Some(vec![Direction::Up, Direction::Down]),
+                West | East => Some(vec![North, South]),
                 _ => None,
             },
             Self::Horizontal => match dir {
-                Direction::Up | Direction::Down => Some(vec![Direction::Left, Direction::Right]),
+                North | South => Some(vec![West, East]),
                 _ => None,
             },
         }
@@ -22,287 +27,260 @@ impl Splitter {
 }
 
 #[derive(Debug)]
-enum Mirror {
+pub enum Mirror {
     LR, // /
     RL, // \
 }
 
 impl Mirror {
-    fn get_next_dir(&self, dir: &Direction) -> Direction {
+    fn get_next_dir(&self, dir: Direction) -> Direction {
         use Direction::*;
         match self {
             Self::LR => match dir {
-                Right => Up,
-                Up => Right,
-                Left => Down,
-                Down => Left,
+                East => North,
+                North => East,
+                West => South,
+                South => West,
             },
             Self::RL => match dir {
-                Right => Down,
-                Down => Right,
-                Left => Up,
-                Up => Left,
+                East => South,
+                South => East,
+                West => North,
+                North => West,
             },
         }
     }
 }
 
 #[derive(Debug)]
-enum Thing {
+pub enum Thing {
     Splitter(Splitter),
     Mirror(Mirror),
     Nothing,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
+type Energized = HashMap<Point<i64>, Vec<Direction>>;
+
+// how a tile looks with the beams drawn over it, a count where several cross
+fn glyph(thing: &Thing, beams: Option<&Vec<Direction>>) -> char {
+    match thing {
+        Thing::Mirror(Mirror::LR) => '/',
+        Thing::Mirror(Mirror::RL) => '\\',
+        Thing::Splitter(Splitter::Vertical) => '|',
+        Thing::Splitter(Splitter::Horizontal) => '-',
+        Thing::Nothing => match beams {
+            Some(dirs) if dirs.len() > 1 => char::from_digit(dirs.len() as u32, 10).unwrap(),
+            Some(dirs) => dirs[0].arrow(),
+            None => '.',
+        },
+    }
 }
 
-impl Direction {
-    fn to_vec(&self) -> (i32, i32) {
-        match self {
-            Self::Up => (0, -1),
-            Self::Right => (1, 0),
-            Self::Down => (0, 1),
-            Self::Left => (-1, 0),
+fn render(map: &Grid<Thing>, energized: &Energized) -> String {
+    let mut out = String::new();
+    for (y, row) in map.rows().enumerate() {
+        for (x, thing) in row.iter().enumerate() {
+            out.push(glyph(thing, energized.get(&Point::new(x as i64, y as i64))));
         }
+        out.push('\n');
     }
+    out
 }
 
-#[allow(dead_code)]
-fn debug_map(map: &Vec<Vec<Thing>>, energized: &HashMap<(i32, i32), Vec<Direction>>) {
-    let height = map.len();
-    let width = map[0].len();
-
-    for y in 0..height {
-        for x in 0..width {
-            match &map[y][x] {
-                Thing::Mirror(mirror) => match mirror {
-                    Mirror::LR => {
-                        print!("/");
-                        continue;
-                    }
-                    Mirror::RL => {
-                        print!("\\");
-                        continue;
-                    }
-                },
-                Thing::Splitter(splitter) => match splitter {
-                    Splitter::Vertical => {
-                        print!("|");
-                        continue;
-                    }
-                    Splitter::Horizontal => {
-                        print!("-");
-                        continue;
-                    }
-                },
-                _ => (),
-            }
-            if let Some(dir) = energized.get(&(x as i32, y as i32)) {
-                if dir.len() > 1 {
-                    print!("{}", dir.len());
-                    continue;
-                }
-                print!(
-                    "{}",
-                    match dir.first().unwrap() {
-                        Direction::Right => ">",
-                        Direction::Left => "<",
-                        Direction::Up => "^",
-                        Direction::Down => "v",
-                    }
-                )
+// the beam from the top left, or the one energizing the most
+#[cfg(feature = "visualize")]
+fn shown_start(map: &Grid<Thing>, part: u8) -> Option<Beam> {
+    match part {
+        1 => Some((Point::new(-1, 0), Direction::East)),
+        _ => starts(map)
+            .into_iter()
+            .max_by_key(|&(pos, dir)| energize(map, pos, dir).len()),
+    }
+}
+
+// the energized tiles filling up as the beams move, with the beams in front
+#[cfg(feature = "visualize")]
+fn record_beams(map: &Grid<Thing>, start: Beam) -> aoc_grid::animation::Recorder {
+    use std::collections::HashSet;
+
+    use aoc_grid::{animation::Recorder, render::Rgb};
+
+    const PALETTE: [Rgb; 4] = [
+        Rgb(25, 25, 35),
+        Rgb(150, 150, 160),
+        Rgb(200, 120, 30),
+        Rgb(255, 240, 150),
+    ];
+    let mut recorder = Recorder::new(map.width(), map.height())
+        .palette(&PALETTE)
+        .delay_ms(40);
+    propagate(map, start, |energized, beams| {
+        let beams = beams.iter().map(|(pos, _)| *pos).collect::<HashSet<_>>();
+        recorder.record(map, |(x, y), thing| {
+            let pos = Point::new(x as i64, y as i64);
+            if beams.contains(&pos) {
+                3
+            } else if energized.contains_key(&pos) {
+                2
+            } else if matches!(thing, Thing::Nothing) {
+                0
             } else {
-                print!(".");
+                1
             }
+        })
+    });
+    recorder
+}
+
+// energized tiles on a heat map of how many beams cross them
+#[cfg(feature = "visualize")]
+fn render_ansi(map: &Grid<Thing>, energized: &Energized, ansi: bool) -> String {
+    use aoc_grid::render::{heat, Cell, Rgb};
+
+    map.render(ansi, |(x, y), thing| {
+        let beams = energized.get(&Point::new(x as i64, y as i64));
+        let cell = Cell::new(glyph(thing, beams));
+        let cell = match thing {
+            Thing::Nothing => cell,
+            _ => cell.bold(),
+        };
+        match beams {
+            Some(dirs) => cell
+                .fg(Rgb(240, 240, 240))
+                .bg(heat(dirs.len() as f64, 1.0, 4.0)),
+            None => cell.fg(Rgb(100, 100, 100)),
         }
-        println!();
-    }
+    })
 }
 
-pub fn process_1(input: &str) -> String {
-    use Direction::*;
-    let map = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '|' => Thing::Splitter(Splitter::Vertical),
-                    '-' => Thing::Splitter(Splitter::Horizontal),
-                    '/' => Thing::Mirror(Mirror::LR),
-                    '\\' => Thing::Mirror(Mirror::RL),
-                    '.' => Thing::Nothing,
-                    _ => unreachable!(),
-                })
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
+pub struct Day16;
 
-    let height = map.len() as i32;
-    let width = map[0].len() as i32;
+impl Solution for Day16 {
+    const DAY: u8 = 16;
 
-    let mut energized: HashMap<(i32, i32), Vec<Direction>> = HashMap::new();
+    type Input<'a> = Grid<Thing>;
 
-    let mut posistions = vec![((-1, 0), Right)];
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Grid::parse(Self::DAY, input, input, "a mirror, splitter or '.'", |c| {
+            Some(match c {
+                '|' => Thing::Splitter(Splitter::Vertical),
+                '-' => Thing::Splitter(Splitter::Horizontal),
+                '/' => Thing::Mirror(Mirror::LR),
+                '\\' => Thing::Mirror(Mirror::RL),
+                '.' => Thing::Nothing,
+                _ => return None,
+            })
+        })
+    }
 
-    loop {
-        // if no more entries to check
-        if posistions.len() == 0 {
-            break;
-        }
-        // if we have already been there or its out of bounds
-        let (mut pos, mut dir) = posistions.pop().unwrap();
-        let dpos = dir.to_vec();
-        pos = (pos.0 + dpos.0, pos.1 + dpos.1);
-
-        if pos.0 < 0
-            || pos.1 < 0
-            || pos.0 >= width
-            || pos.1 >= height
-            || energized.entry(pos).or_insert(Vec::new()).contains(&dir)
-        {
-            continue;
-        }
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        process_1(input).into()
+    }
 
-        energized.entry(pos).or_insert(Vec::new()).push(dir.clone());
-        match &map[pos.1 as usize][pos.0 as usize] {
-            Thing::Splitter(splitter) => {
-                if let Some(dirs) = splitter.get_next_dirs(&dir) {
-                    dirs.iter().for_each(|d| posistions.push((pos, d.clone())));
-                    continue;
-                }
-            }
-            Thing::Mirror(mirror) => {
-                dir = mirror.get_next_dir(&dir);
-            }
-            Thing::Nothing => {}
-        }
-        posistions.push((pos, dir));
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        process_2(input).into()
     }
 
-    energized
-        .iter()
-        .filter(|(_, v)| v.len() > 0)
-        .count()
-        .to_string()
+    #[cfg(feature = "visualize")]
+    fn render(input: &Self::Input<'_>, part: u8, ansi: bool) -> Option<String> {
+        let (pos, dir) = shown_start(input, part)?;
+        Some(render_ansi(input, &energize(input, pos, dir), ansi))
+    }
+
+    #[cfg(feature = "visualize")]
+    fn record(
+        input: &Self::Input<'_>,
+        part: u8,
+        to: &aoc_core::solution::Recording,
+    ) -> Option<std::io::Result<()>> {
+        let start = shown_start(input, part)?;
+        Some(record_beams(input, start).save(to))
+    }
 }
 
-pub fn process_2(input: &str) -> String {
-    use Direction::*;
-    let map = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '|' => Thing::Splitter(Splitter::Vertical),
-                    '-' => Thing::Splitter(Splitter::Horizontal),
-                    '/' => Thing::Mirror(Mirror::LR),
-                    '\\' => Thing::Mirror(Mirror::RL),
-                    '.' => Thing::Nothing,
-                    _ => unreachable!(),
-                })
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
+type Beam = (Point<i64>, Direction);
 
-    let height = map.len() as i32;
-    let width = map[0].len() as i32;
+// every tile a beam goes through, with the directions it goes, for a beam
+// coming in from `start` just off the map
+fn energize(map: &Grid<Thing>, start: Point<i64>, start_dir: Direction) -> Energized {
+    propagate(map, (start, start_dir), |_, _| ())
+}
 
-    let starting_pos = (0..height)
-        .map(|y| (-1, y))
-        .chain((0..height).map(|y| (width, y)))
-        .chain((0..width).map(|x| (x, -1)))
-        .chain((0..width).map(|x| (x, height)))
-        .collect::<Vec<_>>();
+// moves every beam a tile at a time, calling `step` with the tiles energized so
+// far and where the beams are after each move
+fn propagate(
+    map: &Grid<Thing>,
+    start: Beam,
+    mut step: impl FnMut(&Energized, &[Beam]),
+) -> Energized {
+    let mut energized: Energized = HashMap::new();
+    let mut beams = vec![start];
 
-    starting_pos
-        .into_iter()
-        .map(|start_pos| {
-            let start_dir = if start_pos.0 == -1 {
-                Right
-            } else if start_pos.0 == width {
-                Left
-            } else if start_pos.1 == -1 {
-                Down
-            } else if start_pos.1 == height {
-                Up
-            } else {
-                unreachable!()
+    while !beams.is_empty() {
+        let mut next = vec![];
+        for (mut pos, mut dir) in beams {
+            pos += dir.vector();
+            // off the map, or where a beam going the same way has already been
+            let Some(thing) = pos.unsigned().and_then(|p| map.get(p.into())) else {
+                continue;
             };
+            let dirs = energized.entry(pos).or_default();
+            if dirs.contains(&dir) {
+                continue;
+            }
+            dirs.push(dir);
 
-            let mut posistions = vec![(start_pos, start_dir)];
-            let mut energized: HashMap<(i32, i32), Vec<Direction>> = HashMap::new();
-
-            loop {
-                // if no more entries to check
-                if posistions.len() == 0 {
-                    break;
-                }
-                // if we have already been there or its out of bounds
-                let (mut pos, mut dir) = posistions.pop().unwrap();
-                let dpos = dir.to_vec();
-                pos = (pos.0 + dpos.0, pos.1 + dpos.1);
-
-                if pos.0 < 0
-                    || pos.1 < 0
-                    || pos.0 >= width
-                    || pos.1 >= height
-                    || energized.entry(pos).or_insert(Vec::new()).contains(&dir)
-                {
-                    continue;
-                }
-
-                energized.entry(pos).or_insert(Vec::new()).push(dir.clone());
-                match &map[pos.1 as usize][pos.0 as usize] {
-                    Thing::Splitter(splitter) => {
-                        if let Some(dirs) = splitter.get_next_dirs(&dir) {
-                            dirs.iter().for_each(|d| posistions.push((pos, d.clone())));
-                            continue;
-                        }
-                    }
-                    Thing::Mirror(mirror) => {
-                        dir = mirror.get_next_dir(&dir);
+            match thing {
+                Thing::Splitter(splitter) => {
+                    if let Some(dirs) = splitter.get_next_dirs(dir) {
+                        next.extend(dirs.into_iter().map(|d| (pos, d)));
+                        continue;
                     }
-                    Thing::Nothing => {}
                 }
-                posistions.push((pos, dir));
+                Thing::Mirror(mirror) => {
+                    dir = mirror.get_next_dir(dir);
+                }
+                Thing::Nothing => {}
             }
+            next.push((pos, dir));
+        }
+        step(&energized, &next);
+        beams = next;
+    }
+    energized
+}
+
+// every tile just off the map, with the direction that points back onto it
+fn starts(map: &Grid<Thing>) -> Vec<Beam> {
+    use Direction::*;
+    let height = map.height() as i64;
+    let width = map.width() as i64;
+
+    (0..height)
+        .map(|y| (Point::new(-1, y), East))
+        .chain((0..height).map(|y| (Point::new(width, y), West)))
+        .chain((0..width).map(|x| (Point::new(x, -1), South)))
+        .chain((0..width).map(|x| (Point::new(x, height), North)))
+        .collect()
+}
 
-            energized.iter().filter(|(_, v)| v.len() > 0).count()
+fn process_1(map: &Grid<Thing>) -> usize {
+    let energized = energize(map, Point::new(-1, 0), Direction::East);
+    trace!("energized tiles\n{}", render(map, &energized));
+
+    energized.len()
+}
+
+fn process_2(map: &Grid<Thing>) -> usize {
+    starts(map)
+        .into_iter()
+        .map(|(start_pos, start_dir)| {
+            let count = energize(map, start_pos, start_dir).len();
+            debug!(start = ?start_pos, direction = ?start_dir, energized = count);
+            count
         })
         .max()
         .unwrap()
-        .to_string()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = r#".|...\....
-|.-.\.....
-.....|-...
-........|.
-..........
-.........\
-..../.\\..
-.-.-/..|..
-.|....-|.\
-..//.|...."#;
-
-    #[test]
-    fn test_process_1() {
-        let res = process_1(INPUT);
-        assert_eq!("46", res);
-    }
-
-    #[test]
-    fn test_process_2() {
-        let res = process_2(INPUT);
-        assert_eq!("51", res);
-    }
-}
+aoc_core::examples!(Day16);
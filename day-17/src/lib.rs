@@ -1,88 +1,23 @@
-use std::collections::{BinaryHeap, HashMap};
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
-
-impl Direction {
-    fn opposite(&self) -> Self {
-        match self {
-            Self::North => Self::South,
-            Self::South => Self::North,
-            Self::East => Self::West,
-            Self::West => Self::East,
-        }
-    }
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
-struct Point {
-    x: usize,
-    y: usize,
-}
-
-impl Point {
-    fn next_points(&self, grid_width: &usize, grid_height: &usize) -> Vec<(Self, Direction)> {
-        use Direction::*;
-        let mut res = vec![];
-
-        if self.x > 0 {
-            res.push((
-                Point {
-                    x: self.x - 1,
-                    y: self.y,
-                },
-                West,
-            ));
-        }
-        if self.y > 0 {
-            res.push((
-                Point {
-                    x: self.x,
-                    y: self.y - 1,
-                },
-                North,
-            ));
-        }
-        if self.x < grid_width - 1 {
-            res.push((
-                Point {
-                    x: self.x + 1,
-                    y: self.y,
-                },
-                East,
-            ));
-        }
-
-        if self.y < grid_height - 1 {
-            res.push((
-                Point {
-                    x: self.x,
-                    y: self.y + 1,
-                },
-                South,
-            ));
-        }
-        res
-    }
-}
+use aoc_core::{Answer, ParseError, Solution};
+use aoc_grid::{Direction, Grid, Point};
+use aoc_search::{dijkstra, Path};
+use tracing::debug;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 struct Node {
-    position: Point,
+    position: Point<usize>,
     direction: Direction,
     count: u8,
 }
 
 impl Node {
     fn neighbors(&self, grid_width: &usize, grid_height: &usize) -> Vec<Node> {
-        self.position
-            .next_points(grid_width, grid_height)
+        Direction::ALL
             .into_iter()
+            .filter_map(|direction| {
+                let position = self.position.step(direction, *grid_width, *grid_height)?;
+                Some((position, direction))
+            })
             .filter_map(|(position, direction)| {
                 // u can omit going back because weight > 0
                 if direction == self.direction.opposite() {
@@ -107,9 +42,12 @@ impl Node {
     }
 
     fn neighbors_2(&self, grid_width: &usize, grid_height: &usize) -> Vec<Node> {
-        self.position
-            .next_points(grid_width, grid_height)
+        Direction::ALL
             .into_iter()
+            .filter_map(|direction| {
+                let position = self.position.step(direction, *grid_width, *grid_height)?;
+                Some((position, direction))
+            })
             .filter_map(|(position, direction)| {
                 // u can omit going back because weight > 0
                 if direction == self.direction.opposite() {
@@ -134,240 +72,204 @@ impl Node {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct State {
-    node: Node,
-    cost: usize,
-}
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    type Input<'a> = Grid<u8>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Grid::parse(Self::DAY, input, input, "a digit", |c| {
+            c.to_digit(10).map(|d| d as u8)
+        })
+    }
 
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        // min pq
-        other
-            .cost
-            .cmp(&self.cost)
-            .then_with(|| other.node.cmp(&self.node))
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        answer(process_1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        answer(process_2(input))
+    }
+
+    #[cfg(feature = "visualize")]
+    fn render(input: &Self::Input<'_>, part: u8, ansi: bool) -> Option<String> {
+        let route = match part {
+            1 => process_1(input),
+            _ => process_2(input),
+        };
+        Some(render_route(input, route.as_ref(), ansi))
     }
 }
 
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
+// a map too small for the ultra crucible's runs is still a valid one
+fn answer(route: Option<Route>) -> Answer {
+    match route {
+        Some(route) => route.heat_loss.into(),
+        None => "no route to the factory".into(),
     }
 }
 
-#[allow(dead_code)]
-fn debug_grid(grid: &HashMap<Node, usize>, width: &usize, height: &usize) {
-    for y in 0..*height {
-        for x in 0..*width {
-            if let Some(min) = grid
+/// The way the crucible takes from the lava pool to the factory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub heat_loss: usize,
+    /// Every block entered after the top left one, with the way it was entered.
+    pub blocks: Vec<(Point<usize>, Direction)>,
+}
+
+impl Route {
+    fn new(path: Path<Node, usize>) -> Self {
+        let route = Self {
+            heat_loss: path.cost,
+            blocks: path.states[1..]
                 .iter()
-                .filter_map(|(n, v)| {
-                    if n.position == (Point { x, y }) {
-                        Some(v)
-                    } else {
-                        None
-                    }
-                })
-                .min()
-            {
-                print!("{:03} ", min);
-            } else {
-                print!("### ");
+                .map(|node| (node.position, node.direction))
+                .collect(),
+        };
+        debug!(
+            heat_loss = route.heat_loss,
+            blocks = route.blocks.len(),
+            runs = ?route.runs(),
+            "cheapest path"
+        );
+        route
+    }
+
+    /// The straight stretches in order, each as its direction and number of blocks.
+    pub fn runs(&self) -> Vec<(Direction, usize)> {
+        let mut runs: Vec<(Direction, usize)> = vec![];
+        for &(_, direction) in &self.blocks {
+            match runs.last_mut() {
+                Some((last, len)) if *last == direction => *len += 1,
+                _ => runs.push((direction, 1)),
             }
         }
-        println!();
+        runs
     }
 }
 
-pub fn process_1(input: &str) -> String {
-    let grid = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).unwrap() as u8)
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
+// the crucible starts off in either direction without having moved yet
+fn starts() -> [Node; 2] {
+    [Direction::South, Direction::East].map(|direction| Node {
+        position: Point { x: 0, y: 0 },
+        direction,
+        count: 0,
+    })
+}
 
-    let height = grid.len();
-    let width = grid[0].len();
-    let start = Point { x: 0, y: 0 };
+fn process_1(grid: &Grid<u8>) -> Option<Route> {
+    let height = grid.height();
+    let width = grid.width();
     let end = Point {
         x: width - 1,
         y: height - 1,
     };
 
-    let start_1 = Node {
-        position: start.clone(),
-        direction: Direction::South,
-        count: 0,
-    };
-
-    let start_2 = Node {
-        position: start.clone(),
-        direction: Direction::East,
-        count: 0,
-    };
-
-    let mut cache = HashMap::new();
-    cache.insert(start_1.clone(), 0);
-    cache.insert(start_2.clone(), 0);
-
-    let mut pq = BinaryHeap::new();
-
-    pq.push(State {
-        node: start_1,
-        cost: 0,
-    });
-    pq.push(State {
-        node: start_2,
-        cost: 0,
-    });
-
-    while let Some(State { node: u, cost }) = pq.pop() {
-        if u.position == end {
-            return cost.to_string();
-        }
-        for neighbor in u.neighbors(&width, &height) {
-            let new_cost = cost + grid[neighbor.position.y][neighbor.position.x] as usize;
-            if let Some(cur_cost) = cache.get(&neighbor) {
-                if cur_cost >= cur_cost {
-                    continue;
-                }
-            }
-
-            cache.insert(neighbor.clone(), new_cost);
-            pq.push(State {
-                node: neighbor,
-                cost: new_cost,
-            });
-        }
-    }
-    0.to_string()
+    dijkstra(
+        starts(),
+        |u| {
+            u.neighbors(&width, &height).into_iter().map(|neighbor| {
+                let cost = grid[neighbor.position] as usize;
+                (neighbor, cost)
+            })
+        },
+        |u| u.position == end,
+    )
+    .map(Route::new)
 }
 
-pub fn process_2(input: &str) -> String {
-    let grid = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).unwrap() as u8)
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
-
-    let height = grid.len();
-    let width = grid[0].len();
-    let start = Point { x: 0, y: 0 };
+fn process_2(grid: &Grid<u8>) -> Option<Route> {
+    let height = grid.height();
+    let width = grid.width();
     let end = Point {
         x: width - 1,
         y: height - 1,
     };
 
-    let start_1 = Node {
-        position: start.clone(),
-        direction: Direction::South,
-        count: 0,
-    };
-
-    let start_2 = Node {
-        position: start.clone(),
-        direction: Direction::East,
-        count: 0,
-    };
+    dijkstra(
+        starts(),
+        |u| {
+            u.neighbors_2(&width, &height).into_iter().map(|neighbor| {
+                let cost = grid[neighbor.position] as usize;
+                (neighbor, cost)
+            })
+        },
+        // it needs four blocks to stop as well
+        |u| u.position == end && u.count >= 4,
+    )
+    .map(Route::new)
+}
 
-    let mut cache = HashMap::new();
-    cache.insert(start_1.clone(), 0);
-    cache.insert(start_2.clone(), 0);
+// the heat loss digits coloured by how much heat each block loses, with the
+// route drawn over them as arrows
+#[cfg(feature = "visualize")]
+fn render_route(grid: &Grid<u8>, route: Option<&Route>, ansi: bool) -> String {
+    use std::collections::HashMap;
 
-    let mut pq = BinaryHeap::new();
+    use aoc_grid::render::{heat, Cell};
 
-    pq.push(State {
-        node: start_1,
-        cost: 0,
-    });
-    pq.push(State {
-        node: start_2,
-        cost: 0,
+    let arrows = route.map_or(HashMap::new(), |route| {
+        route
+            .blocks
+            .iter()
+            .map(|&(pos, direction)| ((pos.x, pos.y), direction.arrow()))
+            .collect()
     });
 
-    while let Some(State { node: u, cost }) = pq.pop() {
-        if u.position == end {
-            if u.count < 4 {
-                cache.remove(&u);
-            } else {
-                return cost.to_string();
-            }
-        }
-        for neighbor in u.neighbors_2(&width, &height) {
-            let new_cost = cost + grid[neighbor.position.y][neighbor.position.x] as usize;
-            if let Some(cur_cost) = cache.get(&neighbor) {
-                if cur_cost >= cur_cost {
-                    continue;
-                }
-            }
-
-            cache.insert(neighbor.clone(), new_cost);
-            pq.push(State {
-                node: neighbor,
-                cost: new_cost,
-            });
-        }
-    }
-    0.to_string()
+    grid.render(ansi, |pos, loss| {
+        let cell = match arrows.get(&pos) {
+            Some(&arrow) => Cell::new(arrow).bold().highlight(),
+            None => Cell::new(char::from(b'0' + loss)),
+        };
+        cell.bg(heat(*loss as f64, 1.0, 9.0))
+    })
 }
 
+aoc_core::examples!(Day17);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_process_1() {
-        let input = "2413432311323
-3215453535623
-3255245654254
-3446585845452
-4546657867536
-1438598798454
-4457876987766
-3637877979653
-4654967986887
-4564679986453
-1224686865563
-2546548887735
-4322674655533";
-        let res = process_1(input);
-        assert_eq!("102", res);
+    const INPUT: &str = include_str!("../examples/example.in");
+
+    fn check(route: &Route, grid: &Grid<u8>, runs: std::ops::RangeInclusive<usize>) {
+        let heat_loss = route
+            .blocks
+            .iter()
+            .map(|(pos, _)| grid[*pos] as usize)
+            .sum::<usize>();
+        assert_eq!(heat_loss, route.heat_loss);
+        let end = Point::new(grid.width() - 1, grid.height() - 1);
+        assert_eq!(route.blocks.last().map(|(pos, _)| *pos), Some(end));
+        for (direction, len) in route.runs() {
+            assert!(runs.contains(&len), "{} blocks {:?}", len, direction);
+        }
     }
 
     #[test]
-    fn test_process_2_1() {
-        let input = "2413432311323
-3215453535623
-3255245654254
-3446585845452
-4546657867536
-1438598798454
-4457876987766
-3637877979653
-4654967986887
-4564679986453
-1224686865563
-2546548887735
-4322674655533";
-        let res = process_2(input);
-        assert_eq!("94", res);
+    fn test_route() {
+        let grid = Day17::parse(INPUT).unwrap();
+        let route = process_1(&grid).unwrap();
+        assert_eq!(route.heat_loss, 102);
+        check(&route, &grid, 1..=3);
+        let route = process_2(&grid).unwrap();
+        assert_eq!(route.heat_loss, 94);
+        check(&route, &grid, 4..=10);
+
+        let grid = Day17::parse(include_str!("../examples/unfortunate.in")).unwrap();
+        let route = process_2(&grid).unwrap();
+        assert_eq!(route.heat_loss, 71);
+        check(&route, &grid, 4..=10);
     }
 
+    // the ultra crucible can't stop within three blocks of the start
     #[test]
-    fn test_process_2_2() {
-        let input = "111111111111
-999999999991
-999999999991
-999999999991
-999999999991";
-        let res = process_2(input);
-        assert_eq!("71", res);
+    fn test_no_route() {
+        let grid = Day17::parse("12\n34").unwrap();
+        assert_eq!(Day17::part1(&grid), Answer::from(6));
+        assert_eq!(Day17::part2(&grid), Answer::from("no route to the factory"));
     }
 }
@@ -1,214 +1,152 @@
-use std::collections::HashSet;
-
 use nom::{
     branch::alt,
-    bytes::complete::tag,
-    character::complete::{self, alphanumeric1, anychar, newline},
-    multi::{count, separated_list1},
-    sequence::{delimited, terminated, tuple},
-    IResult, Parser,
+    bytes::complete::{tag, take_while_m_n},
+    character::complete,
+    combinator::verify,
+    error::context,
+    sequence::{delimited, terminated},
+    Parser,
 };
 
-use itertools::Itertools;
+use aoc_core::{
+    parse::{parse_lines, IResult},
+    Answer, ParseError, Solution,
+};
+use aoc_grid::{Direction, Point, Polygon};
+use tracing::debug;
 
 #[derive(Debug)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
+pub struct Move<'a> {
+    direction: Direction,
+    amount: i32,
+    color: &'a str,
 }
 
-impl Direction {
-    fn to_vec(&self) -> (i32, i32) {
-        match self {
-            Self::Up => (0, -1),
-            Self::Right => (1, 0),
-            Self::Down => (0, 1),
-            Self::Left => (-1, 0),
+impl Move<'_> {
+    // the real instruction is hidden in the colour: 5 hex digits of distance and a direction
+    fn decode(&self) -> Move<'_> {
+        use Direction::*;
+        let (amount, direction) = self.color.split_at(5);
+        Move {
+            direction: match direction {
+                "0" => East,
+                "1" => South,
+                "2" => West,
+                "3" => North,
+                _ => panic!("invalid direction {}", direction),
+            },
+            amount: i32::from_str_radix(amount, 16).unwrap(),
+            color: self.color,
         }
     }
 }
 
-#[derive(Debug)]
-struct Move {
-    direction: Direction,
-    amount: i32,
-}
-
-fn parse_move(input: &str) -> IResult<&str, Move> {
+fn parse_move(input: &str) -> IResult<&str, Move<'_>> {
     use Direction::*;
     let (input, direction) = terminated(
-        alt((
-            tag("U").map(|_| Up),
-            tag("R").map(|_| Right),
-            tag("D").map(|_| Down),
-            tag("L").map(|_| Left),
-        )),
+        context(
+            "U, R, D or L",
+            alt((
+                tag("U").map(|_| North),
+                tag("R").map(|_| East),
+                tag("D").map(|_| South),
+                tag("L").map(|_| West),
+            )),
+        ),
         tag(" "),
     )(input)?;
     let (input, amount) = terminated(complete::i32, tag(" "))(input)?;
-    let (input, _color) = delimited(tag("(#"), alphanumeric1, tag(")"))(input)?;
-    Ok((input, Move { direction, amount }))
-}
-
-fn parse_move_2(input: &str) -> IResult<&str, Move> {
-    use Direction::*;
-    let (input, _) = terminated(alt((tag("U"), tag("R"), tag("D"), tag("L"))), tag(" "))(input)?;
-    let (input, _) = terminated(complete::i32, tag(" "))(input)?;
-    let (input, (amount, direction)) = delimited(
-        tag("(#"),
-        tuple((
-            count(anychar, 5),
-            alt((
-                tag("0").map(|_| Right),
-                tag("1").map(|_| Down),
-                tag("2").map(|_| Left),
-                tag("3").map(|_| Up),
-            )),
-        )),
-        tag(")"),
+    // the last digit of the color is the direction for part 2
+    let (input, color) = delimited(
+        context("\"(#\"", tag("(#")),
+        context(
+            "six hex digits ending in 0-3",
+            verify(
+                take_while_m_n(6, 6, |c: char| c.is_ascii_hexdigit()),
+                |color: &str| matches!(color.as_bytes()[5], b'0'..=b'3'),
+            ),
+        ),
+        context("\")\"", tag(")")),
     )(input)?;
     Ok((
         input,
         Move {
             direction,
-            amount: i32::from_str_radix(amount.iter().collect::<String>().as_str(), 16).unwrap(),
+            amount,
+            color,
         },
     ))
 }
 
-#[allow(dead_code)]
-fn debug_map(map: &HashSet<(i32, i32)>) {
-    let min_y = map.iter().map(|(_, y)| y).min().unwrap().clone();
-    let max_y = map.iter().map(|(_, y)| y).max().unwrap().clone();
-
-    let min_x = map.iter().map(|(x, _)| x).min().unwrap().clone();
-    let max_x = map.iter().map(|(x, _)| x).max().unwrap().clone();
-    for y in min_y..=max_y {
-        for x in min_x..=max_x {
-            if map.contains(&(x, y)) {
-                print!("#")
-            } else {
-                print!(".")
-            }
-        }
-        println!();
-    }
+// the trench dug by following the moves from the starting cube
+fn lagoon(moves: &[Move]) -> Polygon {
+    let moves = moves
+        .iter()
+        .map(|move_| (move_.direction, move_.amount as i64));
+    Polygon::from_moves(Point::new(0, 0), moves).expect("i32 moves fit an i64")
 }
 
-fn flood_fill(map: &mut HashSet<(i32, i32)>, x: i32, y: i32) {
-    if map.contains(&(x, y)) {
-        return;
-    }
-    map.insert((x, y));
-    flood_fill(map, x - 1, y);
-    flood_fill(map, x + 1, y);
-    flood_fill(map, x, y - 1);
-    flood_fill(map, x, y + 1);
+// the lagoon with every stretch of trench around it in the colour it was dug with
+#[cfg(feature = "visualize")]
+fn plan_svg(moves: &[Move]) -> String {
+    use aoc_grid::{render::Rgb, svg};
+
+    let lagoon = lagoon(moves);
+    // the edges follow the moves, apart from one closing a plan that doesn't
+    svg::polygon(&lagoon, 800.0, Rgb(90, 60, 40), |i| {
+        moves
+            .get(i)
+            .and_then(|move_| Rgb::from_hex(move_.color))
+            .unwrap_or(Rgb(0, 0, 0))
+    })
 }
 
-pub fn process_1(input: &str) -> String {
-    let (_, moves) = separated_list1(newline, parse_move)(input).unwrap();
-    let mut pos = (0, 0);
-    let mut map = moves
-        .iter()
-        .flat_map(|move_| {
-            let v = move_.direction.to_vec();
-            let (x, y) = pos;
-            let new_pos = (x + v.0 * move_.amount, y + v.1 * move_.amount);
-            let x_range = if new_pos.0 > x {
-                x..=new_pos.0
-            } else {
-                new_pos.0..=x
-            };
-
-            let y_range = if new_pos.1 > y {
-                y..=new_pos.1
-            } else {
-                new_pos.1..=y
-            };
-            pos = new_pos;
-            x_range.into_iter().cartesian_product(y_range).collect_vec()
-        })
-        .collect::<HashSet<_>>();
-
-    flood_fill(&mut map, 1, 1);
-
-    (map.iter().count()).to_string()
-}
+pub struct Day18;
 
-pub fn process_2(input: &str) -> String {
-    let (_, moves) = separated_list1(newline, parse_move_2)(input).unwrap();
-    let mut pos = (0, 0);
-    let mut map = moves
-        .iter()
-        .map(|move_| {
-            let v = move_.direction.to_vec();
-            let (x, y) = pos;
-            let (x, y) = (x as i64, y as i64);
-            let new_pos = (
-                x + v.0 as i64 * move_.amount as i64,
-                y + v.1 as i64 * move_.amount as i64,
-            );
-            pos = new_pos;
-            new_pos
-        })
-        .collect::<Vec<_>>();
-
-    map.insert(0, (0, 0));
-
-    let res = map
-        .windows(2)
-        .map(|i| i[0].0 * i[1].1 - i[0].1 * i[1].0)
-        .sum::<i64>();
-
-    let outer = moves.iter().fold(0, |acc, e| acc + e.amount);
-
-    ((res + outer as i64 + 2) / 2).to_string()
-}
+impl Solution for Day18 {
+    const DAY: u8 = 18;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_process_1() {
-        let input = "R 6 (#70c710)
-D 5 (#0dc571)
-L 2 (#5713f0)
-D 2 (#d2c081)
-R 2 (#59c680)
-D 2 (#411b91)
-L 5 (#8ceee2)
-U 2 (#caa173)
-L 1 (#1b58a2)
-U 2 (#caa171)
-R 2 (#7807d2)
-U 3 (#a77fa3)
-L 2 (#015232)
-U 2 (#7a21e3)";
-        let res = process_1(input);
-        assert_eq!("62", res);
+    type Input<'a> = Vec<Move<'a>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_lines(Self::DAY, input, input, parse_move)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        process(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        let moves = input.iter().map(Move::decode).collect::<Vec<_>>();
+        process(&moves).into()
     }
 
-    #[test]
-    fn test_process_2() {
-        let input = "R 6 (#70c710)
-D 5 (#0dc571)
-L 2 (#5713f0)
-D 2 (#d2c081)
-R 2 (#59c680)
-D 2 (#411b91)
-L 5 (#8ceee2)
-U 2 (#caa173)
-L 1 (#1b58a2)
-U 2 (#caa171)
-R 2 (#7807d2)
-U 3 (#a77fa3)
-L 2 (#015232)
-U 2 (#7a21e3)";
-        let res = process_2(input);
-        assert_eq!("952408144115", res);
+    // the plan as part 1 reads it, or as decoded from the colours for part 2
+    #[cfg(feature = "visualize")]
+    fn export(
+        input: &Self::Input<'_>,
+        part: u8,
+        path: &std::path::Path,
+    ) -> Option<std::io::Result<()>> {
+        let svg = match part {
+            1 => plan_svg(input),
+            _ => plan_svg(&input.iter().map(Move::decode).collect::<Vec<_>>()),
+        };
+        Some(std::fs::write(path, svg))
     }
 }
+
+// the trench is the lagoon's boundary, the dug out cubes are the lattice
+// points on and inside it
+fn process(moves: &[Move]) -> i128 {
+    let lagoon = lagoon(moves);
+    debug!(
+        vertices = lagoon.vertices().len(),
+        boundary = lagoon.boundary_points(),
+        interior = lagoon.interior_points(),
+        "lagoon"
+    );
+    lagoon.interior_points() + lagoon.boundary_points()
+}
+
+aoc_core::examples!(Day18);
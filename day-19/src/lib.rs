@@ -1,36 +1,44 @@
 use std::collections::HashMap;
 
+use aoc_core::{
+    parse::{parse_all, IResult},
+    Answer, ParseError, Solution,
+};
+use aoc_interval::{Interval, IntervalSet};
+use aoc_parse::{blank_line, field, lines, unsigned};
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{self, alpha1, anychar, newline},
+    character::complete::{alpha1, one_of},
+    error::context,
     multi::separated_list1,
-    sequence::{delimited, preceded, tuple},
-    IResult, Parser,
+    sequence::{delimited, preceded, separated_pair, tuple},
+    Parser,
 };
+use tracing::trace;
 
 #[derive(Debug)]
-enum When {
+pub enum When {
     LT(u32),
     GT(u32),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Where<'a> {
+pub enum Where<'a> {
     Reject,
     Accept,
     Next(&'a str),
 }
 
 #[derive(Debug)]
-struct Rule<'a> {
+pub struct Rule<'a> {
     what: char,
     when: When,
     r#where: Where<'a>,
 }
 
 impl<'a> Rule<'a> {
-    fn check(&self, part: &Part) -> Option<Where> {
+    fn check(&self, part: &Part) -> Option<Where<'_>> {
         let v = match self.what {
             'x' => part.x,
             'm' => part.m,
@@ -50,16 +58,33 @@ impl<'a> Rule<'a> {
             None
         }
     }
+
+    // the ratings this rule sends on, and the ones left for the next rule
+    fn split(&self, ratings: &Ratings) -> (Ratings, Ratings) {
+        let i = "xmas".find(self.what).unwrap();
+        let (matching, rest) = match self.when {
+            When::LT(x) => ratings[i].split_at(x.into()),
+            When::GT(x) => {
+                let (below, above) = ratings[i].split_at(u64::from(x) + 1);
+                (above, below)
+            }
+        };
+        let mut sent = ratings.clone();
+        sent[i] = matching;
+        let mut kept = ratings.clone();
+        kept[i] = rest;
+        (sent, kept)
+    }
 }
 
 #[derive(Debug)]
-struct Workflow<'a> {
+pub struct Workflow<'a> {
     rules: Vec<Rule<'a>>,
     r#final: Where<'a>,
 }
 
 impl<'a> Workflow<'a> {
-    fn next_workflow(&self, part: &Part) -> Where {
+    fn next_workflow(&self, part: &Part) -> Where<'_> {
         for rule in &self.rules {
             if let Some(where_) = rule.check(part) {
                 return where_;
@@ -69,19 +94,22 @@ impl<'a> Workflow<'a> {
     }
 }
 
+// the values left for x, m, a and s
+type Ratings = [IntervalSet<u64>; 4];
+
 #[derive(Debug)]
-struct Part {
+pub struct Part {
     x: u32,
     m: u32,
     a: u32,
     s: u32,
 }
 
-fn parse_rule(input: &str) -> IResult<&str, Rule> {
+fn parse_rule(input: &str) -> IResult<&str, Rule<'_>> {
     let (input, (what, when, amount, where_)) = tuple((
-        anychar,
-        alt((tag("<"), tag(">"))),
-        complete::u32,
+        context("x, m, a or s", one_of("xmas")),
+        context("'<' or '>'", alt((tag("<"), tag(">")))),
+        unsigned,
         preceded(tag(":"), alpha1).map(|e| match e {
             "A" => Where::Accept,
             "R" => Where::Reject,
@@ -103,7 +131,7 @@ fn parse_rule(input: &str) -> IResult<&str, Rule> {
     ))
 }
 
-fn parse_workflow(input: &str) -> IResult<&str, (&str, Workflow)> {
+fn parse_workflow(input: &str) -> IResult<&str, (&str, Workflow<'_>)> {
     let (input, name) = alpha1(input)?;
     let (input, (rules, final_)) = delimited(
         tag("{"),
@@ -137,10 +165,10 @@ fn parse_part(input: &str) -> IResult<&str, Part> {
     let (input, (x, m, a, s)) = delimited(
         tag("{"),
         tuple((
-            preceded(tag("x="), complete::u32),
-            preceded(tag(",m="), complete::u32),
-            preceded(tag(",a="), complete::u32),
-            preceded(tag(",s="), complete::u32),
+            field("x", unsigned),
+            preceded(tag(","), field("m", unsigned)),
+            preceded(tag(","), field("a", unsigned)),
+            preceded(tag(","), field("s", unsigned)),
         )),
         tag("}"),
     )(input)?;
@@ -148,17 +176,36 @@ fn parse_part(input: &str) -> IResult<&str, Part> {
     Ok((input, Part { x, m, a, s }))
 }
 
-pub fn process_1(input: &str) -> String {
-    let mut lines = input.split("\n\n");
-    let workflows = separated_list1(newline, parse_workflow)(lines.next().unwrap())
-        .unwrap()
-        .1
-        .into_iter()
-        .collect::<HashMap<_, _>>();
-    let (_, parts) = separated_list1(newline, parse_part)(lines.next().unwrap()).unwrap();
+type System<'a> = (HashMap<&'a str, Workflow<'a>>, Vec<Part>);
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+
+    type Input<'a> = System<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let (workflows, parts) = parse_all(
+            Self::DAY,
+            input,
+            separated_pair(lines(parse_workflow), blank_line, lines(parse_part)),
+        )?;
+        Ok((workflows.into_iter().collect(), parts))
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        process_1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        process_2(input).into()
+    }
+}
 
+fn process_1((workflows, parts): &System) -> u32 {
     parts
-        .into_iter()
+        .iter()
         .filter_map(|part| {
             let mut workflow_type = Where::Next("in");
 
@@ -174,52 +221,44 @@ pub fn process_1(input: &str) -> String {
                     Where::Next(name) => name,
                     _ => unreachable!(),
                 };
-                //println!("{}: {}", part.x, workflow_name);
+                trace!(?part, workflow = workflow_name);
 
                 let workflow = workflows.get(&workflow_name).unwrap();
-                workflow_type = workflow.next_workflow(&part);
+                workflow_type = workflow.next_workflow(part);
             }
         })
         .sum::<u32>()
-        .to_string()
-}
-
-pub fn process_2(_input: &str) -> String {
-    todo!();
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_process_1() {
-        let input = "px{a<2006:qkq,m>2090:A,rfg}
-pv{a>1716:R,A}
-lnx{m>1548:A,A}
-rfg{s<537:gd,x>2440:R,A}
-qs{s>3448:A,lnx}
-qkq{x<1416:A,crn}
-crn{x>2662:A,R}
-in{s<1351:px,qqz}
-qqz{s>2770:qs,m<1801:hdj,R}
-gd{a>3333:R,R}
-hdj{m>838:A,pv}
-
-{x=787,m=2655,a=1222,s=2876}
-{x=1679,m=44,a=2067,s=496}
-{x=2036,m=264,a=79,s=2244}
-{x=2461,m=1339,a=466,s=291}
-{x=2127,m=1623,a=2188,s=1013}";
-        let res = process_1(input);
-        assert_eq!("19114", res);
+// how many of the combinations in `ratings` end up accepted from `where_` on
+fn accepted(workflows: &HashMap<&str, Workflow>, where_: Where, ratings: Ratings) -> u64 {
+    if ratings.iter().any(IntervalSet::is_empty) {
+        return 0;
     }
-
-    #[test]
-    #[ignore]
-    fn test_process_2() {
-        let input = "";
-        let res = process_2(input);
-        assert_eq!("", res);
+    match where_ {
+        Where::Reject => 0,
+        Where::Accept => ratings.iter().map(IntervalSet::len).product(),
+        Where::Next(name) => {
+            let workflow = workflows.get(name).unwrap();
+            let mut ratings = ratings;
+            let mut total = 0;
+            for rule in &workflow.rules {
+                let (sent, kept) = rule.split(&ratings);
+                total += accepted(workflows, rule.r#where, sent);
+                ratings = kept;
+            }
+            total + accepted(workflows, workflow.r#final, ratings)
+        }
     }
 }
+
+fn process_2((workflows, _): &System) -> u64 {
+    let all = IntervalSet::from(Interval::new(1, 4001));
+    accepted(
+        workflows,
+        Where::Next("in"),
+        [all.clone(), all.clone(), all.clone(), all],
+    )
+}
+
+aoc_core::examples!(Day19);
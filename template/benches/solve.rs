@@ -0,0 +1,43 @@
+//! `cargo bench -p {{crate}}`, a quick look while working on the day.
+//! `aoc bench {{day}}` keeps the numbers and compares them to a baseline.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use aoc_core::{input::InputLoader, Solution};
+use {{crate_name}}::{{struct}};
+
+const ITERATIONS: u32 = 10;
+
+fn time<T>(name: &str, mut f: impl FnMut() -> T) {
+    let mut total = Duration::ZERO;
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        black_box(f());
+        total += start.elapsed();
+    }
+    println!("{:<6} {:?}", name, total / ITERATIONS);
+}
+
+fn main() {
+    let input = match InputLoader::new({{struct}}::DAY).stdin(false).load() {
+        Ok(input) => input.text,
+        Err(err) => {
+            eprintln!("{}", err);
+            return;
+        }
+    };
+    let parsed = match {{struct}}::parse(&input) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("{}", err);
+            return;
+        }
+    };
+
+    time("parse", || {{struct}}::parse(&input));
+    time("part1", || {{struct}}::part1(&parsed));
+    time("part2", || {{struct}}::part2(&parsed));
+}
@@ -1,27 +1,45 @@
-pub fn process_1(_input: &str) -> String {
-    todo!();
-}
+use aoc_core::{parse::parse_lines, Answer, ParseError, Solution};
+use aoc_parse::signed_list;
+
+pub struct {{struct}};
+
+impl Solution for {{struct}} {
+    const DAY: u8 = {{day}};
+
+    type Input<'a> = Vec<Vec<i64>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_lines(Self::DAY, input, input, signed_list)
+    }
 
-pub fn process_2(_input: &str) -> String {
-    todo!();
+    fn part1(_input: &Self::Input<'_>) -> Answer {
+        todo!();
+    }
+
+    fn part2(_input: &Self::Input<'_>) -> Answer {
+        todo!();
+    }
 }
 
+// answers go in `examples/<name>.part1` and `.part2`, one test each
+aoc_core::examples!({{struct}});
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = include_str!("../examples/example.in");
+
     #[test]
-    fn test_process_1() {
-        let input = "";
-        let res = process_1(input);
-        assert_eq!("", res);
+    fn test_parse() {
+        let input = {{struct}}::parse(EXAMPLE).unwrap();
+        assert_eq!(input, vec![vec![1, 2, 3], vec![4, 5, 6]]);
     }
 
     #[test]
-    #[ignore]
-    fn test_process_2() {
-        let input = "";
-        let res = process_2(input);
-        assert_eq!("", res);
+    fn test_parse_error() {
+        let err = {{struct}}::parse("1 2\nx").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "a number");
     }
 }